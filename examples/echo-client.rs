@@ -11,10 +11,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let ctx = zmq::Context::new();
 
-    let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER)?;
-    sock.connect(&opt.addr)?;
-
-    let sock = zmq_async::Socket::new(sock).await?;
+    let sock =
+        zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::DEALER, &opt.addr).await?;
     sock.send_multipart(&["hi"]).await?;
     println!("Sent");
     let msgs = sock.recv_multipart_as::<Vec<u8>>().await?;
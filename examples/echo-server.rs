@@ -11,14 +11,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let ctx = zmq::Context::new();
 
-    let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER)?;
-    sock.bind(&opt.addr)?;
-    let sock = zmq_async::Socket::new(sock).await?;
+    let sock = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::ROUTER, &opt.addr).await?;
+    sock.set_router_mandatory(true)?;
 
     loop {
-        let msgs = sock.recv_multipart().await?;
-        println!("Received: {:?}", msgs);
-        sock.send_multipart(msgs).await?;
-        println!("Sent");
+        let env = zmq_async::Envelope::recv(&sock).await?;
+        println!("Received: {:?}", env.payload());
+        match env.reply(&sock, env.payload()).await {
+            Ok(()) => println!("Sent"),
+            Err(e) if e.kind() == std::io::ErrorKind::HostUnreachable => {
+                println!("The client has gone away")
+            }
+            Err(e) => return Err(e.into()),
+        }
     }
 }
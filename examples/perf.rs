@@ -82,6 +82,12 @@ enum Mode {
     },
     /// Receive messages.
     Recv {
+        /// Receive into `zmq::Message` without copying the frames.
+        #[structopt(short = "z", long = "zero-copy")]
+        zero_copy: bool,
+        /// Receive up to this number of messages at once.
+        #[structopt(short = "B", long = "batch")]
+        batch: Option<usize>,
         #[structopt(flatten)]
         cfg: SocketOption,
     },
@@ -149,7 +155,7 @@ impl Perf {
             if let Some(prev) = self.time.take() {
                 let diff = (now - prev).as_millis();
                 let kcount = self.count as u128 * 1000;
-                let rate = if diff != 0 { kcount / diff } else { 0 };
+                let rate = kcount.checked_div(diff).unwrap_or(0);
                 println!(
                     "{} items/sec ({} items/{} msec; total {} msgs)",
                     rate, self.count, diff, self.total
@@ -162,23 +168,22 @@ impl Perf {
     }
 }
 
-fn setup_sock(ctx: &zmq::Context, cfg: &SocketOption) -> zmq::Result<zmq::Socket> {
-    let sock = ctx.socket(cfg.sock.into())?;
+async fn setup_sock(ctx: &zmq::Context, cfg: &SocketOption) -> std::io::Result<zmq_async::Socket> {
+    let mut builder = zmq_async::SocketBuilder::new(ctx, cfg.sock.into());
     if let Some(sndhwm) = cfg.sndhwm {
-        sock.set_sndhwm(sndhwm)?;
+        builder = builder.sndhwm(sndhwm);
     }
     if let Some(rcvhwm) = cfg.rcvhwm {
-        sock.set_rcvhwm(rcvhwm)?;
+        builder = builder.rcvhwm(rcvhwm);
     }
     if cfg.bind {
-        sock.bind(&cfg.addr)?;
+        builder.bind(&cfg.addr).await
     } else {
-        sock.connect(&cfg.addr)?;
+        builder.connect(&cfg.addr).await
     }
-    Ok(sock)
 }
 
-#[tokio::main(basic_scheduler)]
+#[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opt = Opt::from_args();
 
@@ -190,35 +195,51 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Mode::Send { cfg } => {
             println!("Running as sender");
 
-            let sock = setup_sock(&ctx, &cfg)?;
-            let sock = zmq_async::Socket::new(sock).await?;
+            let sock = setup_sock(&ctx, &cfg).await?;
 
             let mut i = 0u8;
             loop {
                 i = i.wrapping_add(1);
-                sock.send_multipart(&vec![vec![i]; 1]).await?;
+                sock.send(&[i]).await?;
                 perf.rate();
             }
         }
-        Mode::Recv { cfg } => {
+        Mode::Recv {
+            cfg,
+            zero_copy,
+            batch,
+        } => {
             println!("Running as receiver");
 
-            let sock = setup_sock(&ctx, &cfg)?;
-            let sock = zmq_async::Socket::new(sock).await?;
+            let sock = setup_sock(&ctx, &cfg).await?;
 
             let mut i = 0u8;
+            let mut msg = vec![];
             loop {
+                if let Some(max) = batch {
+                    for msg in sock.recv_batch(max).await? {
+                        i = i.wrapping_add(1);
+                        assert_eq!(msg, vec![vec![i]; 1]);
+                        perf.rate();
+                    }
+                    continue;
+                }
+
                 i = i.wrapping_add(1);
-                let msg = sock.recv_multipart().await?;
-                assert_eq!(msg, vec![vec![i]; 1]);
+                if zero_copy {
+                    let msgs = sock.recv_multipart_msg().await?;
+                    assert!(msgs.len() == 1 && msgs[0][..] == [i]);
+                } else {
+                    sock.recv_multipart_into(&mut msg).await?;
+                    assert_eq!(msg, vec![vec![i]; 1]);
+                }
                 perf.rate();
             }
         }
         Mode::EchoServer { cfg } => {
             println!("Running as echo server");
 
-            let sock = setup_sock(&ctx, &cfg)?;
-            let sock = zmq_async::Socket::new(sock).await?;
+            let sock = setup_sock(&ctx, &cfg).await?;
 
             loop {
                 let msg = sock.recv_multipart().await?;
@@ -229,8 +250,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Mode::EchoClient { cfg, depth } => {
             println!("Running as echo client");
 
-            let sock = setup_sock(&ctx, &cfg)?;
-            let sock = zmq_async::Socket::new(sock).await?;
+            let sock = setup_sock(&ctx, &cfg).await?;
 
             let mut i = 0u8;
             loop {
@@ -254,8 +274,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Mode::Dump { cfg, fmt } => {
             println!("Running as receiver");
 
-            let sock = setup_sock(&ctx, &cfg)?;
-            let sock = zmq_async::Socket::new(sock).await?;
+            let sock = setup_sock(&ctx, &cfg).await?;
 
             loop {
                 let msg = sock.recv_multipart().await?;
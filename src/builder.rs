@@ -0,0 +1,139 @@
+use crate::Socket;
+use std::{convert::TryFrom, io, time::Duration};
+
+/// Builder which sets socket options up before making the socket async.
+pub struct SocketBuilder<'a> {
+    ctx: &'a zmq::Context,
+    kind: zmq::SocketType,
+    sndhwm: Option<i32>,
+    rcvhwm: Option<i32>,
+    identity: Option<Vec<u8>>,
+    linger: Option<i32>,
+    rcvtimeo: Option<Duration>,
+    sndtimeo: Option<Duration>,
+    conflate: Option<bool>,
+}
+
+impl<'a> SocketBuilder<'a> {
+    /// Start building a socket of the given type.
+    pub fn new(ctx: &'a zmq::Context, kind: zmq::SocketType) -> Self {
+        Self {
+            ctx,
+            kind,
+            sndhwm: None,
+            rcvhwm: None,
+            identity: None,
+            linger: None,
+            rcvtimeo: None,
+            sndtimeo: None,
+            conflate: None,
+        }
+    }
+
+    /// Set the value of SNDHWM socket option.
+    pub fn sndhwm(mut self, sndhwm: i32) -> Self {
+        self.sndhwm = Some(sndhwm);
+        self
+    }
+
+    /// Set the value of RCVHWM socket option.
+    pub fn rcvhwm(mut self, rcvhwm: i32) -> Self {
+        self.rcvhwm = Some(rcvhwm);
+        self
+    }
+
+    /// Set the value of ROUTING_ID (a.k.a. IDENTITY) socket option.
+    pub fn identity(mut self, identity: &[u8]) -> Self {
+        self.identity = Some(identity.to_vec());
+        self
+    }
+
+    /// Set the value of LINGER socket option in milliseconds.
+    pub fn linger(mut self, linger: i32) -> Self {
+        self.linger = Some(linger);
+        self
+    }
+
+    /// Set how long the async receives wait for a message, as with [`Socket::set_rcvtimeo`].
+    pub fn recv_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.rcvtimeo = timeout;
+        self
+    }
+
+    /// Set how long the async sends wait for the socket to accept a message, as with
+    /// [`Socket::set_sndtimeo`].
+    pub fn send_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.sndtimeo = timeout;
+        self
+    }
+
+    /// Set the receive timeout in milliseconds (`-1` for none).
+    ///
+    /// This used to set RCVTIMEO of libzmq, which never applies to the async socket.
+    /// It now sets the timeout of the async receives.
+    #[deprecated(note = "use `recv_timeout`")]
+    pub fn rcvtimeo(self, rcvtimeo: i32) -> Self {
+        self.recv_timeout(millis(rcvtimeo))
+    }
+
+    /// Set the send timeout in milliseconds (`-1` for none).
+    ///
+    /// This used to set SNDTIMEO of libzmq, which never applies to the async socket.
+    /// It now sets the timeout of the async sends.
+    #[deprecated(note = "use `send_timeout`")]
+    pub fn sndtimeo(self, sndtimeo: i32) -> Self {
+        self.send_timeout(millis(sndtimeo))
+    }
+
+    /// Set the value of CONFLATE socket option, to keep only the last message in the queues.
+    pub fn conflate(mut self, conflate: bool) -> Self {
+        self.conflate = Some(conflate);
+        self
+    }
+
+    /// Create the socket and connect it to `endpoint`.
+    pub async fn connect(self, endpoint: &str) -> io::Result<Socket> {
+        let sock = self.build()?;
+        sock.connect(endpoint)?;
+        self.finish(sock).await
+    }
+
+    /// Create the socket and bind it to `endpoint`.
+    pub async fn bind(self, endpoint: &str) -> io::Result<Socket> {
+        let sock = self.build()?;
+        sock.bind(endpoint)?;
+        self.finish(sock).await
+    }
+
+    fn build(&self) -> zmq::Result<zmq::Socket> {
+        let sock = self.ctx.socket(self.kind)?;
+        if let Some(sndhwm) = self.sndhwm {
+            sock.set_sndhwm(sndhwm)?;
+        }
+        if let Some(rcvhwm) = self.rcvhwm {
+            sock.set_rcvhwm(rcvhwm)?;
+        }
+        if let Some(identity) = &self.identity {
+            sock.set_identity(identity)?;
+        }
+        if let Some(linger) = self.linger {
+            sock.set_linger(linger)?;
+        }
+        if let Some(conflate) = self.conflate {
+            sock.set_conflate(conflate)?;
+        }
+        Ok(sock)
+    }
+
+    async fn finish(&self, sock: zmq::Socket) -> io::Result<Socket> {
+        let sock = Socket::new(sock).await?;
+        sock.set_rcvtimeo(self.rcvtimeo);
+        sock.set_sndtimeo(self.sndtimeo);
+        Ok(sock)
+    }
+}
+
+/// Turn a timeout in milliseconds, negative for none, into a duration.
+fn millis(ms: i32) -> Option<Duration> {
+    u64::try_from(ms).ok().map(Duration::from_millis)
+}
@@ -0,0 +1,108 @@
+use crate::Socket;
+use futures::{ready, Sink, Stream};
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Conversion between items and multi-part messages.
+///
+/// Unlike byte-stream codecs, a codec works on whole messages,
+/// so there's no need to find the item boundaries.
+pub trait Codec {
+    /// The item type.
+    type Item;
+
+    /// Encode an item into a multi-part message.
+    fn encode(&mut self, item: Self::Item) -> Vec<Vec<u8>>;
+
+    /// Decode an item from a multi-part message.
+    fn decode(&mut self, msgs: Vec<Vec<u8>>) -> io::Result<Self::Item>;
+}
+
+/// A socket which sends and receives items through a [`Codec`].
+///
+/// The sink side buffers a single message as [`Outgoing`](crate::Outgoing) does.
+pub struct Framed<C> {
+    sock: Socket,
+    codec: C,
+    pending: Option<Vec<Vec<u8>>>,
+}
+
+impl<C> Framed<C> {
+    /// Wrap `sock` to send and receive items with `codec`.
+    pub fn new(sock: Socket, codec: C) -> Self {
+        Self {
+            sock,
+            codec,
+            pending: None,
+        }
+    }
+
+    /// Provides reference to the underlying socket.
+    pub fn get_ref(&self) -> &Socket {
+        &self.sock
+    }
+
+    /// Provides reference to the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Provides mutable reference to the codec.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Take the underlying socket out, dropping the message not flushed yet.
+    pub fn into_inner(self) -> Socket {
+        self.sock
+    }
+}
+
+impl<C> Stream for Framed<C>
+where
+    C: Codec + Unpin,
+{
+    type Item = io::Result<C::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let res = ready!(this.sock.poll_recv_multipart(cx));
+        Poll::Ready(Some(res.and_then(|msgs| this.codec.decode(msgs))))
+    }
+}
+
+impl<C> Sink<C::Item> for Framed<C>
+where
+    C: Codec + Unpin,
+{
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: C::Item) -> io::Result<()> {
+        let this = self.get_mut();
+        this.pending = Some(this.codec.encode(item));
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if let Some(msgs) = &this.pending {
+            ready!(this.sock.poll_send_multipart(cx, msgs))?;
+            this.pending = None;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
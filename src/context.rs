@@ -0,0 +1,68 @@
+use crate::{waker::WeakTaskWaker, Socket};
+use std::{io, sync::Mutex};
+
+/// A ZMQ context which keeps track of the async sockets created in it.
+pub struct Context {
+    ctx: zmq::Context,
+    sockets: Mutex<Vec<[WeakTaskWaker; 2]>>,
+}
+
+impl Context {
+    /// Create a new ZMQ context.
+    pub fn new() -> Self {
+        Self::from(zmq::Context::new())
+    }
+
+    /// Provides reference to the underlying context object.
+    pub fn context(&self) -> &zmq::Context {
+        &self.ctx
+    }
+
+    /// Create an async socket of the given type in this context.
+    pub async fn socket(&self, kind: zmq::SocketType) -> io::Result<Socket> {
+        let sock = Socket::new(self.ctx.socket(kind)?).await?;
+
+        let mut sockets = self.sockets.lock().unwrap();
+        sockets.retain(|[read, _]| read.is_alive());
+        sockets.push([sock.inner.read.downgrade(), sock.inner.write.downgrade()]);
+
+        Ok(sock)
+    }
+
+    /// Terminate the context.
+    ///
+    /// Once the termination starts, every operation on the sockets of this context
+    /// fails with an error of kind `BrokenPipe`, and the tasks waiting on the sockets
+    /// created by [`socket`](Self::socket) are woken up to see it. The sockets are
+    /// closed by dropping them, and this returns once all of them have been.
+    /// The blocking part of the termination runs on a blocking thread.
+    pub async fn terminate(self) -> io::Result<()> {
+        let Self { mut ctx, sockets } = self;
+
+        let destroy = crate::reactor::spawn_blocking(move || ctx.destroy());
+
+        for wakers in sockets.into_inner().unwrap().iter() {
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+
+        destroy.await??;
+        Ok(())
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<zmq::Context> for Context {
+    fn from(ctx: zmq::Context) -> Self {
+        Self {
+            ctx,
+            sockets: Mutex::new(Vec::new()),
+        }
+    }
+}
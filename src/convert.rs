@@ -0,0 +1,267 @@
+use std::{convert::Infallible, error::Error, fmt, string::FromUtf8Error};
+
+/// Conversion from a received message part.
+pub trait FromMessage {
+    /// Convert a message part into `Self`.
+    fn from(msg: zmq::Message) -> Self;
+}
+
+impl FromMessage for zmq::Message {
+    fn from(msg: zmq::Message) -> Self {
+        msg
+    }
+}
+
+impl FromMessage for Vec<u8> {
+    fn from(msg: zmq::Message) -> Self {
+        msg.to_vec()
+    }
+}
+
+impl FromMessage for Box<[u8]> {
+    fn from(msg: zmq::Message) -> Self {
+        msg.to_vec().into_boxed_slice()
+    }
+}
+
+/// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+impl FromMessage for String {
+    fn from(msg: zmq::Message) -> Self {
+        String::from_utf8_lossy(&msg).into_owned()
+    }
+}
+
+/// Fallible conversion from a received message part.
+pub trait TryFromMessage: Sized {
+    /// The error returned when the conversion fails.
+    type Error;
+
+    /// Try to convert a message part into `Self`.
+    fn try_from(msg: zmq::Message) -> Result<Self, Self::Error>;
+}
+
+impl TryFromMessage for zmq::Message {
+    type Error = Infallible;
+
+    fn try_from(msg: zmq::Message) -> Result<Self, Self::Error> {
+        Ok(msg)
+    }
+}
+
+impl TryFromMessage for Vec<u8> {
+    type Error = Infallible;
+
+    fn try_from(msg: zmq::Message) -> Result<Self, Self::Error> {
+        Ok(msg.to_vec())
+    }
+}
+
+impl TryFromMessage for Box<[u8]> {
+    type Error = Infallible;
+
+    fn try_from(msg: zmq::Message) -> Result<Self, Self::Error> {
+        Ok(msg.to_vec().into_boxed_slice())
+    }
+}
+
+/// Fails if the part is not valid UTF-8.
+impl TryFromMessage for String {
+    type Error = FromUtf8Error;
+
+    fn try_from(msg: zmq::Message) -> Result<Self, Self::Error> {
+        String::from_utf8(msg.to_vec())
+    }
+}
+
+/// Wraps the received part without copying it.
+///
+/// The part itself is kept alive by the `Bytes` and its clones.
+#[cfg(feature = "bytes")]
+impl FromMessage for bytes::Bytes {
+    fn from(msg: zmq::Message) -> Self {
+        bytes::Bytes::from_owner(Part(msg))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl TryFromMessage for bytes::Bytes {
+    type Error = Infallible;
+
+    fn try_from(msg: zmq::Message) -> Result<Self, Self::Error> {
+        Ok(FromMessage::from(msg))
+    }
+}
+
+/// A received part owned by a `Bytes`.
+#[cfg(feature = "bytes")]
+struct Part(zmq::Message);
+
+#[cfg(feature = "bytes")]
+impl AsRef<[u8]> for Part {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Conversion into a message part to send.
+pub trait ToMessage {
+    /// Convert `self` into a message part.
+    fn to_message(self) -> zmq::Message;
+}
+
+impl ToMessage for zmq::Message {
+    fn to_message(self) -> zmq::Message {
+        self
+    }
+}
+
+/// The buffer is handed over to libzmq without copying.
+impl ToMessage for Vec<u8> {
+    fn to_message(self) -> zmq::Message {
+        self.into()
+    }
+}
+
+/// The buffer is handed over to libzmq without copying.
+impl ToMessage for Box<[u8]> {
+    fn to_message(self) -> zmq::Message {
+        self.into()
+    }
+}
+
+/// The buffer is handed over to libzmq without copying.
+impl ToMessage for String {
+    fn to_message(self) -> zmq::Message {
+        self.into_bytes().into()
+    }
+}
+
+/// The buffer is handed over to libzmq without copying if this is its only handle
+/// and it was allocated as a `Vec` or a `BytesMut`. Otherwise, e.g. for a `Bytes`
+/// shared with clones, made from a static slice or received with [`FromMessage`],
+/// the data is copied.
+#[cfg(feature = "bytes")]
+impl ToMessage for bytes::Bytes {
+    fn to_message(self) -> zmq::Message {
+        let buf: Vec<u8> = self.into();
+        buf.into()
+    }
+}
+
+/// The buffer is handed over to libzmq without copying, unless it's shared
+/// with the `Bytes` or `BytesMut` split from it, in which case it's copied.
+#[cfg(feature = "bytes")]
+impl ToMessage for bytes::BytesMut {
+    fn to_message(self) -> zmq::Message {
+        let buf: Vec<u8> = self.into();
+        buf.into()
+    }
+}
+
+/// The error returned when a part doesn't have the size of the integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidLength {
+    /// The size of the integer.
+    pub expected: usize,
+    /// The size of the part.
+    pub actual: usize,
+}
+
+impl fmt::Display for InvalidLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a {}-byte part, got {} bytes",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for InvalidLength {}
+
+macro_rules! impl_from_message_int {
+    ($($t:ty),*) => {
+        $(
+            /// The part is read as a big-endian integer. A shorter part is zero-extended,
+            /// and only the last bytes of a longer part are used, as with an `as` cast.
+            impl FromMessage for $t {
+                fn from(msg: zmq::Message) -> Self {
+                    const N: usize = std::mem::size_of::<$t>();
+                    let mut buf = [0u8; N];
+                    let len = msg.len().min(N);
+                    buf[N - len..].copy_from_slice(&msg[msg.len() - len..]);
+                    <$t>::from_be_bytes(buf)
+                }
+            }
+
+            /// The part is read as a big-endian integer and must have exactly its size.
+            impl TryFromMessage for $t {
+                type Error = InvalidLength;
+
+                fn try_from(msg: zmq::Message) -> Result<Self, Self::Error> {
+                    let mut buf = [0u8; std::mem::size_of::<$t>()];
+                    if msg.len() != buf.len() {
+                        return Err(InvalidLength {
+                            expected: buf.len(),
+                            actual: msg.len(),
+                        });
+                    }
+                    buf.copy_from_slice(&msg);
+                    Ok(<$t>::from_be_bytes(buf))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_message_int!(u16, u32, u64, i32, i64);
+
+/// Conversion from all the parts of a received message.
+pub trait FromMultipart: Sized {
+    /// Convert the parts of a message into `Self`.
+    fn from_multipart(frames: Vec<zmq::Message>) -> Result<Self, MultipartError>;
+}
+
+/// The error returned when a message doesn't have the expected number of parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultipartError {
+    /// The expected number of parts.
+    pub expected: usize,
+    /// The number of the parts received.
+    pub actual: usize,
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a {}-part message, got {} parts",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for MultipartError {}
+
+macro_rules! impl_from_multipart_tuple {
+    ($n:expr; $($t:ident),*) => {
+        /// Each part is converted with [`FromMessage`].
+        impl<$($t: FromMessage),*> FromMultipart for ($($t,)*) {
+            fn from_multipart(frames: Vec<zmq::Message>) -> Result<Self, MultipartError> {
+                if frames.len() != $n {
+                    return Err(MultipartError {
+                        expected: $n,
+                        actual: frames.len(),
+                    });
+                }
+                let mut frames = frames.into_iter();
+                Ok(($($t::from(frames.next().unwrap()),)*))
+            }
+        }
+    };
+}
+
+impl_from_multipart_tuple!(1; A);
+impl_from_multipart_tuple!(2; A, B);
+impl_from_multipart_tuple!(3; A, B, C);
+impl_from_multipart_tuple!(4; A, B, C, D);
@@ -0,0 +1,57 @@
+use crate::Socket;
+use std::io;
+
+/// A message received on a ROUTER socket, split into the routing frames and the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    routing: Vec<Vec<u8>>,
+    payload: Vec<Vec<u8>>,
+}
+
+impl Envelope {
+    /// Receive a message and split it at the first empty delimiter frame.
+    ///
+    /// The routing frames include the delimiter. If there's no delimiter
+    /// (e.g. the peer is a DEALER sending raw frames), only the first frame
+    /// is taken as the identity.
+    pub async fn recv(sock: &Socket) -> io::Result<Self> {
+        let mut frames = sock.recv_multipart().await?;
+
+        let at = frames
+            .iter()
+            .position(|f| f.is_empty())
+            .map_or(1, |i| i + 1)
+            .min(frames.len());
+        let payload = frames.split_off(at);
+
+        Ok(Self {
+            routing: frames,
+            payload,
+        })
+    }
+
+    /// The identity frames, followed by the delimiter if there was one.
+    pub fn routing(&self) -> &[Vec<u8>] {
+        &self.routing
+    }
+
+    /// The frames after the routing frames.
+    pub fn payload(&self) -> &[Vec<u8>] {
+        &self.payload
+    }
+
+    /// Take the payload out of the envelope.
+    pub fn into_payload(self) -> Vec<Vec<u8>> {
+        self.payload
+    }
+
+    /// Send `payload` back to the peer the envelope has been received from.
+    pub async fn reply<T>(&self, sock: &Socket, payload: &[T]) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        let routing = self.routing.iter().map(|f| f.as_slice());
+        sock.send_all(routing.chain(payload.iter().map(|f| f.as_ref())))
+            .await
+    }
+}
@@ -0,0 +1,79 @@
+use std::{error, fmt, io};
+
+/// An error of a socket operation, telling the errors reported by libzmq apart.
+///
+/// The methods of [`Socket`](crate::Socket) return `io::Error`, which keeps the
+/// `zmq::Error` it has been made from. Convert it into this to match on the libzmq error
+/// (e.g. EFSM or EHOSTUNREACH); it converts back into the same `io::Error`.
+#[derive(Debug)]
+pub enum Error {
+    /// An error reported by libzmq.
+    Zmq(zmq::Error),
+    /// Any other error, e.g. a malformed message.
+    Io(io::Error),
+}
+
+impl Error {
+    /// The libzmq error, if it's one.
+    pub fn zmq(&self) -> Option<zmq::Error> {
+        match self {
+            Error::Zmq(e) => Some(*e),
+            Error::Io(_) => None,
+        }
+    }
+
+    /// The kind of the `io::Error` this converts into.
+    ///
+    /// A terminated context (ETERM) is reported as `BrokenPipe`, and a message
+    /// a ROUTER can't route (EHOSTUNREACH) as `HostUnreachable`.
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            Error::Zmq(zmq::Error::ETERM) => io::ErrorKind::BrokenPipe,
+            Error::Zmq(zmq::Error::EHOSTUNREACH) => io::ErrorKind::HostUnreachable,
+            Error::Zmq(e) => io::Error::from(*e).kind(),
+            Error::Io(e) => e.kind(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Zmq(e) => e.fmt(f),
+            Error::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Zmq(e) => Some(e),
+            Error::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<zmq::Error> for Error {
+    fn from(e: zmq::Error) -> Self {
+        Error::Zmq(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.get_ref().and_then(|e| e.downcast_ref::<zmq::Error>()) {
+            Some(e) => Error::Zmq(*e),
+            None => Error::Io(e),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Zmq(inner) => io::Error::new(e.kind(), inner),
+            Error::Io(e) => e,
+        }
+    }
+}
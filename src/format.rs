@@ -0,0 +1,47 @@
+use crate::Socket;
+use serde::{de::DeserializeOwned, Serialize};
+use std::io;
+
+/// The serialization format of single-part messages, for [`Socket::send_as`]
+/// and [`Socket::recv_as`].
+///
+/// The variants are available with the features of the same serializers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireFormat {
+    /// JSON, as in [`Socket::send_json`].
+    #[cfg(feature = "serde_json")]
+    Json,
+    /// MessagePack, as in [`Socket::send_msgpack`].
+    #[cfg(feature = "rmp-serde")]
+    MsgPack,
+}
+
+impl Socket {
+    /// Serialize `v` in `format` and send it as a single-part message.
+    pub async fn send_as<T>(&self, v: &T, format: WireFormat) -> io::Result<()>
+    where
+        T: Serialize,
+    {
+        match format {
+            #[cfg(feature = "serde_json")]
+            WireFormat::Json => self.send_json(v).await,
+            #[cfg(feature = "rmp-serde")]
+            WireFormat::MsgPack => self.send_msgpack(v).await,
+        }
+    }
+
+    /// Receive a single-part message and deserialize it from `format`.
+    ///
+    /// Returns an error of kind `InvalidData` if the message can't be deserialized into `T`.
+    pub async fn recv_as<T>(&self, format: WireFormat) -> io::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        match format {
+            #[cfg(feature = "serde_json")]
+            WireFormat::Json => self.recv_json().await,
+            #[cfg(feature = "rmp-serde")]
+            WireFormat::MsgPack => self.recv_msgpack().await,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::Socket;
+use std::io;
+
+/// A part of a multi-part message, with whether more parts followed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// The data of the part.
+    pub data: Vec<u8>,
+    /// The value of RCVMORE read right after receiving the part.
+    pub more: bool,
+}
+
+impl Socket {
+    /// Receive a multi-part message, keeping the RCVMORE flag of each part.
+    ///
+    /// The flag is read for every part, including the last one, so that protocol code
+    /// can check the framing by itself. [`recv_multipart`](Self::recv_multipart) is
+    /// cheaper when the flags aren't needed. Should a part fail to be received after
+    /// the first one, the rest of the message is discarded before the error is returned.
+    pub async fn recv_frames(&self) -> io::Result<Vec<Frame>> {
+        self.recv_with(recv_frames).await
+    }
+}
+
+fn recv_frames(sock: &zmq::Socket) -> zmq::Result<Vec<Frame>> {
+    let mut frames = vec![];
+    loop {
+        let part = sock
+            .recv_bytes(zmq::DONTWAIT)
+            .and_then(|data| Ok((data, sock.get_rcvmore()?)));
+        let (data, more) = match part {
+            Ok(part) => part,
+            Err(e) if frames.is_empty() => return Err(e),
+            Err(e) => {
+                let _ = crate::discard_rest(sock);
+                return Err(e);
+            }
+        };
+        frames.push(Frame { data, more });
+        if !more {
+            return Ok(frames);
+        }
+    }
+}
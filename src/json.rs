@@ -0,0 +1,26 @@
+use crate::Socket;
+use serde::{de::DeserializeOwned, Serialize};
+use std::io;
+
+impl Socket {
+    /// Serialize `v` into JSON and send it as a single-part message.
+    pub async fn send_json<T>(&self, v: &T) -> io::Result<()>
+    where
+        T: Serialize,
+    {
+        let buf =
+            serde_json::to_vec(v).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.send(buf).await
+    }
+
+    /// Receive a single-part message and deserialize it from JSON.
+    ///
+    /// Returns an error of kind `InvalidData` if the message can't be deserialized into `T`.
+    pub async fn recv_json<T>(&self) -> io::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let msg = self.recv().await?;
+        serde_json::from_slice(&msg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
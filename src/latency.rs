@@ -0,0 +1,69 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// Records the durations of operations, e.g. request/reply round trips.
+///
+/// All the samples are kept until [`reset`](Self::reset), and sorted on [`report`](Self::report).
+#[derive(Debug, Clone, Default)]
+pub struct Latency {
+    samples: Vec<Duration>,
+}
+
+/// The distribution of the durations recorded by a [`Latency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyReport {
+    /// The number of samples.
+    pub count: usize,
+    /// The shortest duration.
+    pub min: Duration,
+    /// The longest duration.
+    pub max: Duration,
+    /// The median.
+    pub p50: Duration,
+    /// The 99th percentile.
+    pub p99: Duration,
+}
+
+impl Latency {
+    /// Create an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a duration measured elsewhere.
+    pub fn record(&mut self, dur: Duration) {
+        self.samples.push(dur);
+    }
+
+    /// Run `fut` to completion, recording how long it took.
+    pub async fn measure<F: Future>(&mut self, fut: F) -> F::Output {
+        let start = Instant::now();
+        let output = fut.await;
+        self.record(start.elapsed());
+        output
+    }
+
+    /// Get the distribution of the samples, or `None` if there's none.
+    pub fn report(&mut self) -> Option<LatencyReport> {
+        self.samples.sort_unstable();
+
+        let samples = &self.samples;
+        // The nearest-rank percentile.
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+
+        Some(LatencyReport {
+            count: samples.len(),
+            min: *samples.first()?,
+            max: *samples.last()?,
+            p50: percentile(50),
+            p99: percentile(99),
+        })
+    }
+
+    /// Forget all the samples.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
@@ -1,45 +1,350 @@
-mod evented;
+mod builder;
+mod codec;
+mod context;
+mod convert;
+mod envelope;
+mod error;
+#[cfg(any(feature = "serde_json", feature = "rmp-serde"))]
+mod format;
+mod frame;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "metrics")]
+mod latency;
+mod lvc;
+mod message;
+mod monitor;
+#[cfg(feature = "rmp-serde")]
+mod msgpack;
+mod options;
+mod pair;
+mod proxy;
+mod reactor;
+mod ready;
+mod security;
+mod select;
+mod sink;
+mod split;
+mod stats;
+mod stream;
+mod topic;
+mod waker;
 
-use crate::evented::Evented;
+use crate::{
+    reactor::Reactor,
+    stats::{Batch, Counters, Transfer},
+    waker::TaskWaker,
+};
 use futures::future::poll_fn;
-use mio::Ready;
 use std::{
-    cell::RefCell,
+    cell::Cell,
+    ffi::CString,
+    fmt,
+    future::Future,
     io,
-    task::{Context, Poll, Waker},
+    os::raw::c_void,
+    rc::Rc,
+    task::{self, Poll},
+    time::Duration,
 };
-use tokio::io::PollEvented;
+use tokio::time::Instant;
 
+pub use crate::builder::SocketBuilder;
+pub use crate::codec::{Codec, Framed};
+pub use crate::context::Context;
+pub use crate::convert::{
+    FromMessage, FromMultipart, InvalidLength, MultipartError, ToMessage, TryFromMessage,
+};
+pub use crate::envelope::Envelope;
+pub use crate::error::Error;
+#[cfg(any(feature = "serde_json", feature = "rmp-serde"))]
+pub use crate::format::WireFormat;
+pub use crate::frame::Frame;
+#[cfg(feature = "metrics")]
+pub use crate::latency::{Latency, LatencyReport};
+pub use crate::lvc::LastValueCache;
+pub use crate::message::MessageBuilder;
+pub use crate::monitor::MonitorStream;
+pub use crate::pair::pair;
+pub use crate::proxy::{proxy, proxy_steerable};
+pub use crate::ready::{Interest, Readiness};
+pub use crate::security::curve_keypair;
+pub use crate::select::{recv_any, SocketGroup};
+pub use crate::sink::Outgoing;
+pub use crate::split::{RecvHalf, SendHalf};
+pub use crate::stats::SocketStats;
+pub use crate::stream::{StreamEvent, StreamIo, StreamSocket};
+pub use crate::topic::{Subscription, TopicStream};
 pub use zmq;
 
+/// How long [`Socket::close`] waits for the queue to drain when LINGER is -1.
+pub const CLOSE_FLUSH_LIMIT: Duration = Duration::from_secs(30);
+
+/// An async ZMQ socket.
+///
+/// The socket is driven by Tokio, or by `async-io` with the `async-io` feature
+/// (e.g. for async-std or smol). The API is the same either way.
+///
+/// # Cancel safety
+///
+/// A message is received or sent within a single poll of the future, and handed out
+/// in that same poll. Dropping a pending receive (e.g. the losing branch of a `select!`)
+/// never loses a message nor leaves a message half-received; the next receive gets it.
+/// The same goes for sends, except that [`send_all`](Self::send_all) may leave
+/// a partial message pending, as documented there. [`request`](Self::request) is
+/// not cancel-safe once the request has been sent.
+///
+/// # Sharing
+///
+/// Clones share the same underlying socket, so several tasks can send and receive
+/// through it. ZMQ sockets are not thread-safe, so `Socket` is `!Send` and all the
+/// clones must stay on the thread which created it (e.g. tasks spawned on a
+/// `tokio::task::LocalSet`).
+///
+/// Mutable access to the underlying socket needs the only handle:
+/// [`socket_mut`](Self::socket_mut) panics while clones are alive, so code which
+/// clones the socket should use [`try_socket_mut`](Self::try_socket_mut) instead.
+#[derive(Clone)]
 pub struct Socket {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    // Dropped first, so that the fd is deregistered before the socket closes it.
+    reactor: Reactor,
     sock: zmq::Socket,
-    evented: PollEvented<Evented>,
-    read: RefCell<Option<Waker>>,
-    write: RefCell<Option<Waker>>,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    fd: reactor::RawFd,
+    // The raw handle of `sock`, for the options the `zmq` crate doesn't know.
+    raw: *mut c_void,
+    read: TaskWaker,
+    write: TaskWaker,
+    events: Cell<Option<zmq::PollEvents>>,
+    // The directions woken up by `cross_wake` which no task has slept on since.
+    cross_woken: Cell<zmq::PollEvents>,
+    // Whether a send has been held back since the socket last accepted one.
+    blocked: Cell<bool>,
+    stats: Counters,
+    rcvtimeo: Cell<Option<Duration>>,
+    sndtimeo: Cell<Option<Duration>>,
+    spin: Cell<u32>,
 }
 
 impl Socket {
     /// Create a async socket instance from `zmq::Socket`
+    ///
+    /// This doesn't wait for anything; use [`from_socket`](Self::from_socket)
+    /// outside of async code.
     pub async fn new(sock: zmq::Socket) -> io::Result<Self> {
-        let evented = PollEvented::new(Evented::new(sock.get_fd()?))?;
+        Self::from_socket(sock)
+    }
+
+    /// Create a async socket instance from `zmq::Socket` without an async context.
+    ///
+    /// The socket is registered to the reactor of the current Tokio runtime, so this
+    /// must be called within one (e.g. in `Runtime::enter`), and the socket must be used
+    /// with that runtime. With the `async-io` feature, the socket is registered to
+    /// the global reactor of `async-io` instead and works on any executor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of the context of a Tokio runtime,
+    /// unless the `async-io` feature is enabled.
+    pub fn from_socket(sock: zmq::Socket) -> io::Result<Self> {
+        let fd = sock.get_fd()?;
+        Self::with_fd(fd, sock)
+    }
+
+    /// Create a async socket instance from `zmq::Socket` and its ZMQ_FD obtained elsewhere,
+    /// e.g. from the C library which handed out the socket.
+    ///
+    /// `fd` must be the ZMQ_FD of `sock`, or the socket is never woken up, and it stays
+    /// owned by the socket. This is the same as [`from_socket`](Self::from_socket) otherwise.
+    #[cfg(unix)]
+    pub fn from_fd_and_socket(fd: std::os::unix::io::RawFd, sock: zmq::Socket) -> io::Result<Self> {
+        Self::with_fd(fd, sock)
+    }
+
+    fn with_fd(fd: reactor::RawFd, mut sock: zmq::Socket) -> io::Result<Self> {
+        let (read, write) = (TaskWaker::new(), TaskWaker::new());
+        let reactor = Reactor::new(fd, &read, &write)?;
 
         Ok(Self {
-            sock,
-            evented,
-            read: RefCell::new(None),
-            write: RefCell::new(None),
+            inner: Rc::new(Inner {
+                reactor,
+                fd,
+                raw: sock.as_mut_ptr(),
+                sock,
+                read,
+                write,
+                events: Cell::new(None),
+                cross_woken: Cell::new(zmq::PollEvents::empty()),
+                blocked: Cell::new(false),
+                stats: Counters::default(),
+                rcvtimeo: Cell::new(None),
+                sndtimeo: Cell::new(None),
+                spin: Cell::new(0),
+            }),
         })
     }
 
+    /// Create a socket of the given type and connect it to `endpoint`.
+    ///
+    /// Use [`SocketBuilder`] to set socket options before connecting.
+    pub async fn connect(
+        ctx: &zmq::Context,
+        kind: zmq::SocketType,
+        endpoint: &str,
+    ) -> io::Result<Self> {
+        SocketBuilder::new(ctx, kind).connect(endpoint).await
+    }
+
+    /// Create a socket of the given type and bind it to `endpoint`.
+    ///
+    /// Use [`SocketBuilder`] to set socket options before binding.
+    pub async fn bind(
+        ctx: &zmq::Context,
+        kind: zmq::SocketType,
+        endpoint: &str,
+    ) -> io::Result<Self> {
+        SocketBuilder::new(ctx, kind).bind(endpoint).await
+    }
+
+    /// Connect the socket to another endpoint.
+    ///
+    /// [`connect`](Self::connect) is the constructor, hence the name. Like the other
+    /// topology changes below, this wakes up the waiting tasks, as the socket may have
+    /// become ready without an event on the fd.
+    pub fn connect_to(&self, endpoint: &str) -> io::Result<()> {
+        let res = self.inner.sock.connect(endpoint);
+        self.topology_changed();
+        res.map_err(|e| self.error(e))
+    }
+
+    /// Connect the socket to each of `endpoints` in turn, stopping at the first failure.
+    ///
+    /// The error tells which endpoint failed and how many were connected before it,
+    /// and keeps the kind of the original error. The waiting tasks are woken up once,
+    /// at the end.
+    pub fn connect_all(&self, endpoints: &[&str]) -> io::Result<()> {
+        let res = endpoints.iter().enumerate().try_for_each(|(i, endpoint)| {
+            self.inner.sock.connect(endpoint).map_err(|e| {
+                let e = self.error(e);
+                io::Error::new(
+                    e.kind(),
+                    format!(
+                        "failed to connect to {} ({} of {} endpoints connected): {}",
+                        endpoint,
+                        i,
+                        endpoints.len(),
+                        e
+                    ),
+                )
+            })
+        });
+        self.topology_changed();
+        res
+    }
+
+    /// Connect the socket to each of `endpoints`, returning the outcome for each of them.
+    ///
+    /// Unlike [`connect_all`](Self::connect_all), a failure doesn't stop the others
+    /// from being connected.
+    pub fn connect_each(&self, endpoints: &[&str]) -> Vec<io::Result<()>> {
+        let res = endpoints
+            .iter()
+            .map(|endpoint| self.inner.sock.connect(endpoint).map_err(|e| self.error(e)))
+            .collect();
+        self.topology_changed();
+        res
+    }
+
+    /// Disconnect the socket from an endpoint it has been connected to.
+    pub fn disconnect(&self, endpoint: &str) -> io::Result<()> {
+        let res = self.inner.sock.disconnect(endpoint);
+        self.topology_changed();
+        res.map_err(|e| self.error(e))
+    }
+
+    /// Disconnect from `endpoint` and connect to it again.
+    ///
+    /// This forces a fresh connection (and handshake), e.g. to recover from a stuck peer.
+    /// If the socket wasn't connected to `endpoint`, it's just connected.
+    pub fn reconnect(&self, endpoint: &str) -> io::Result<()> {
+        let res = match self.inner.sock.disconnect(endpoint) {
+            Ok(()) | Err(zmq::Error::ENOENT) => self.inner.sock.connect(endpoint),
+            Err(e) => Err(e),
+        };
+        self.topology_changed();
+        res.map_err(|e| self.error(e))
+    }
+
+    /// Bind the socket to another endpoint.
+    ///
+    /// [`bind`](Self::bind) is the constructor, hence the name.
+    pub fn bind_to(&self, endpoint: &str) -> io::Result<()> {
+        let res = self.inner.sock.bind(endpoint);
+        self.topology_changed();
+        res.map_err(|e| self.error(e))
+    }
+
+    /// Unbind the socket from an endpoint it has been bound to.
+    ///
+    /// A wildcard endpoint (e.g. `tcp://*:*`) has to be unbound by the endpoint it
+    /// resolved to, as returned by [`last_endpoint`](Self::last_endpoint).
+    pub fn unbind(&self, endpoint: &str) -> io::Result<()> {
+        let res = unbind(self.inner.raw, endpoint);
+        self.topology_changed();
+        res.map_err(|e| self.error(e))
+    }
+
     /// Provides reference to the underlying socket object.
     pub fn socket(&self) -> &zmq::Socket {
-        &self.sock
+        self.inner.events.set(None);
+        &self.inner.sock
     }
 
     /// Provides mutable reference to the underlying socket object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the socket is shared with clones; see
+    /// [`try_socket_mut`](Self::try_socket_mut).
     pub fn socket_mut(&mut self) -> &mut zmq::Socket {
-        &mut self.sock
+        self.try_socket_mut()
+            .expect("the socket is shared with clones: drop the clones or use try_socket_mut")
+    }
+
+    /// Provides mutable reference to the underlying socket object, unless it's shared.
+    ///
+    /// Returns `None` if the socket is shared with clones.
+    pub fn try_socket_mut(&mut self) -> Option<&mut zmq::Socket> {
+        let inner = Rc::get_mut(&mut self.inner)?;
+        inner.events.set(None);
+        Some(&mut inner.sock)
+    }
+
+    /// Send a single-part message.
+    pub async fn send<T>(&self, msg: T) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        let msg = msg.as_ref();
+        self.send_with(|sock| sock.send(msg, zmq::DONTWAIT).map(|_| msg.len()))
+            .await?;
+        Ok(())
+    }
+
+    /// Send a single-part message referencing `data` without copying it.
+    ///
+    /// This is for constant frames sent at a high rate, e.g. protocol markers.
+    /// To combine such parts with others in a multi-part message, add them with
+    /// [`MessageBuilder::constant`] and send it with [`send_built`](Self::send_built).
+    pub async fn send_const(&self, data: &'static [u8]) -> io::Result<()> {
+        let raw = self.inner.raw;
+        self.send_with(|_| send_const(raw, data, zmq::DONTWAIT).map(|_| data.len()))
+            .await?;
+        Ok(())
     }
 
     /// Send a multi-part message.
@@ -47,13 +352,635 @@ impl Socket {
     where
         T: AsRef<[u8]>,
     {
-        let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_ref()).collect();
-        poll_fn(|cx| self.poll_write(cx, &msgs)).await
+        self.send_all(msgs).await
+    }
+
+    /// Send a multi-part message taking the parts from an iterator.
+    ///
+    /// Each part is sent with SNDMORE except the last one, which is found by
+    /// pulling one part ahead of the one being sent. ZMQ delivers multi-part
+    /// messages atomically: peers get either all the parts or none of them,
+    /// and nothing is delivered until the last part has been sent. Should the socket
+    /// stop accepting parts halfway, the future waits and resumes from the part that
+    /// failed; dropping it then leaves a partial message pending in the socket.
+    pub async fn send_all<I, T>(&self, frames: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut frames = frames.into_iter();
+        let mut cur = frames.next();
+        let mut next = frames.next();
+        let mut sent = 0;
+
+        self.send_with(|sock| {
+            while let Some(frame) = &cur {
+                let flags = match next {
+                    Some(_) => zmq::DONTWAIT | zmq::SNDMORE,
+                    None => zmq::DONTWAIT,
+                };
+                sock.send(frame.as_ref(), flags)?;
+                sent += frame.as_ref().len();
+                cur = next.take();
+                next = frames.next();
+            }
+            Ok(sent)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Send as many of the multi-part messages `msgs` as the socket accepts right now.
+    ///
+    /// Returns the number of messages sent from the start of `msgs`, waiting only
+    /// while none of them can be sent; retry the rest with the messages not sent.
+    /// An error after the first message also just ends the batch, and is returned
+    /// when the rest is retried.
+    pub async fn send_batch(&self, msgs: &[Vec<Vec<u8>>]) -> io::Result<usize> {
+        if msgs.is_empty() {
+            return Ok(0);
+        }
+
+        let batch = self
+            .send_with(|sock| {
+                let mut batch = Batch {
+                    messages: 0,
+                    bytes: 0,
+                };
+                for msg in msgs {
+                    match sock.send_multipart(msg, zmq::DONTWAIT) {
+                        Ok(()) => {
+                            batch.messages += 1;
+                            batch.bytes += stats::len(msg);
+                        }
+                        // libzmq accepts the rest of a message once the first part is in.
+                        // The messages already sent must be reported, so a failure only
+                        // ends the batch; it shows again when the rest is retried.
+                        Err(_) if batch.messages > 0 => break,
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok(batch)
+            })
+            .await?;
+        Ok(batch.messages)
+    }
+
+    /// Send a multi-part message made of `zmq::Message`s without copying them.
+    ///
+    /// The parts can also be anything else converted with [`ToMessage`].
+    /// libzmq takes the messages over, e.g. to forward the parts received by
+    /// [`recv_multipart_msg`](Self::recv_multipart_msg). A part it fails to take is
+    /// dropped, so if the socket stops accepting the parts (e.g. the peer has gone away
+    /// in the meantime) this fails with an error of kind `WouldBlock` instead of waiting.
+    pub async fn send_messages<T>(&self, msgs: Vec<T>) -> io::Result<()>
+    where
+        T: ToMessage,
+    {
+        let mut msgs = msgs.into_iter().map(ToMessage::to_message).peekable();
+        let mut sent = 0;
+
+        let res = self
+            .send_with(|sock| {
+                while let Some(msg) = msgs.next() {
+                    let flags = match msgs.peek() {
+                        Some(_) => zmq::DONTWAIT | zmq::SNDMORE,
+                        None => zmq::DONTWAIT,
+                    };
+                    let len = msg.len();
+                    match sock.send(msg, flags) {
+                        Ok(()) => sent += len,
+                        Err(zmq::Error::EAGAIN) => return Ok(None),
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok(Some(sent))
+            })
+            .await?;
+
+        res.map(drop).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "the socket stopped accepting the message, which has been dropped",
+            )
+        })
+    }
+
+    /// Send a multi-part message with a part for each slice.
+    ///
+    /// libzmq has no scatter-gather send, so each slice is still sent as a part
+    /// by [`send_all`](Self::send_all); this is for callers which already hold
+    /// `IoSlice`s. ZMQ treats the parts as a single atomic multi-part message.
+    pub async fn send_vectored(&self, frames: &[io::IoSlice<'_>]) -> io::Result<()> {
+        self.send_all(frames.iter().map(|f| &f[..])).await
+    }
+
+    /// Receive a single-part message.
+    ///
+    /// If a multi-part message arrives, the remaining parts are discarded
+    /// and an error of kind `InvalidData` is returned.
+    pub async fn recv(&self) -> io::Result<zmq::Message> {
+        let msg = self
+            .recv_with(|sock| {
+                let msg = sock.recv_msg(zmq::DONTWAIT)?;
+                if msg.get_more() {
+                    discard_rest(sock)?;
+                }
+                Ok(msg)
+            })
+            .await?;
+
+        if msg.get_more() {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "received a multi-part message",
+            ))
+        } else {
+            Ok(msg)
+        }
+    }
+
+    /// Send a single-part text message.
+    pub async fn send_str(&self, s: &str) -> io::Result<()> {
+        self.send(s).await
+    }
+
+    /// Receive a single-part text message.
+    ///
+    /// Returns an error of kind `InvalidData` if the message is not valid UTF-8,
+    /// or has multiple parts as with [`recv`](Self::recv).
+    pub async fn recv_string(&self) -> io::Result<String> {
+        let msg = self.recv().await?;
+        <String as TryFromMessage>::try_from(msg)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
     /// Receive a multi-part message.
+    ///
+    /// This is cancel-safe: if the future is dropped, no message has been taken
+    /// from the socket.
     pub async fn recv_multipart(&self) -> io::Result<Vec<Vec<u8>>> {
-        poll_fn(|cx| self.poll_read(cx)).await
+        self.recv_with(|sock| sock.recv_multipart(zmq::DONTWAIT))
+            .await
+    }
+
+    /// Receive a multi-part message, telling whether it ended cleanly.
+    ///
+    /// This is for diagnosing framing problems. The flag is `true` when the last part
+    /// had RCVMORE cleared. A part which fails to arrive or a RCVMORE which can't be read
+    /// after the first part ends the message early with the parts received so far
+    /// and `false`, instead of an error. libzmq delivers multi-part messages atomically,
+    /// so that only happens if the socket fails halfway (e.g. the context is terminated).
+    pub async fn recv_multipart_verbose(&self) -> io::Result<(Vec<Vec<u8>>, bool)> {
+        self.recv_with(recv_verbose).await
+    }
+
+    /// Receive up to `max` multi-part messages at once.
+    ///
+    /// This waits for the first message, then takes whatever else has already arrived,
+    /// so that a burst of messages costs a single wakeup. Returns an error of kind
+    /// `InvalidInput` if `max` is zero.
+    pub async fn recv_batch(&self, max: usize) -> io::Result<Vec<Vec<Vec<u8>>>> {
+        if max == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a batch must hold at least one message",
+            ));
+        }
+
+        self.recv_with(|sock| {
+            let mut batch = vec![sock.recv_multipart(zmq::DONTWAIT)?];
+            while batch.len() < max {
+                match sock.recv_multipart(zmq::DONTWAIT) {
+                    Ok(msgs) => batch.push(msgs),
+                    // Keep the messages already received; a real error shows up next time.
+                    Err(_) => break,
+                }
+            }
+            Ok(batch)
+        })
+        .await
+    }
+
+    /// Receive a multi-part message of at most `max_frames` parts.
+    ///
+    /// If the message has more parts, the rest of it is discarded and an error of kind
+    /// `InvalidData` is returned. Only `max_frames` parts are ever held in memory.
+    /// Returns an error of kind `InvalidInput` if `max_frames` is zero.
+    pub async fn recv_multipart_limited(&self, max_frames: usize) -> io::Result<Vec<Vec<u8>>> {
+        if max_frames == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a message must be allowed at least one part",
+            ));
+        }
+
+        let msgs = self
+            .recv_with(|sock| {
+                let mut msgs = vec![];
+                loop {
+                    if msgs.len() == max_frames {
+                        discard_rest(sock)?;
+                        return Ok(None);
+                    }
+                    let msg = sock.recv_msg(zmq::DONTWAIT)?;
+                    msgs.push(msg.to_vec());
+                    if !msg.get_more() {
+                        return Ok(Some(msgs));
+                    }
+                }
+            })
+            .await?;
+
+        msgs.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("received a message of more than {} parts", max_frames),
+            )
+        })
+    }
+
+    /// Receive a multi-part message into `buf`.
+    ///
+    /// The frames already in `buf` are overwritten in place to reuse their allocations,
+    /// and `buf` is truncated to the number of the received frames.
+    pub async fn recv_multipart_into(&self, buf: &mut Vec<Vec<u8>>) -> io::Result<()> {
+        self.recv_with(|sock| recv_into(sock, buf)).await?;
+        Ok(())
+    }
+
+    /// Receive a multi-part message without copying the parts.
+    pub async fn recv_multipart_msg(&self) -> io::Result<Vec<zmq::Message>> {
+        self.recv_with(recv_msgs).await
+    }
+
+    /// Receive a multi-part message into `buf` without copying the parts.
+    ///
+    /// The `zmq::Message`s already in `buf` are received into, and `buf` is truncated to
+    /// the number of the received parts. This saves allocating the `Vec` and the messages
+    /// themselves, but not the data: libzmq replaces the content of a message on receive,
+    /// so parts too large to be held inline in it are allocated by libzmq whatever `buf` holds.
+    pub async fn recv_multipart_msg_into(&self, buf: &mut Vec<zmq::Message>) -> io::Result<()> {
+        self.recv_with(|sock| recv_msgs_into(sock, buf)).await?;
+        Ok(())
+    }
+
+    /// Receive a multi-part message, handing each part to `f` as it's received.
+    ///
+    /// `f` is called in the task awaiting this, with the part and whether it's the last one.
+    /// The parts are received into a single `zmq::Message` in turn, so no `Vec` is built.
+    /// Should a part fail to be received once `f` has been called, the rest of the message
+    /// is discarded before the error is returned, leaving the socket at the next message.
+    pub async fn recv_multipart_with<F>(&self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(&[u8], bool),
+    {
+        self.recv_with(|sock| recv_each(sock, &mut f)).await?;
+        Ok(())
+    }
+
+    /// Receive a multi-part message, converting each part into `T`.
+    pub async fn recv_multipart_as<T>(&self) -> io::Result<Vec<T>>
+    where
+        T: FromMessage,
+    {
+        let msgs = self.recv_multipart_msg().await?;
+        Ok(msgs.into_iter().map(FromMessage::from).collect())
+    }
+
+    /// Receive a multi-part message, converting each part into `T` strictly.
+    ///
+    /// The whole message is received even if a part fails to convert,
+    /// then an error of kind `InvalidData` is returned.
+    pub async fn recv_multipart_try_as<T>(&self) -> io::Result<Vec<T>>
+    where
+        T: TryFromMessage,
+        T::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let msgs = self.recv_multipart_msg().await?;
+        msgs.into_iter()
+            .map(|msg| T::try_from(msg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+            .collect()
+    }
+
+    /// Receive a multi-part message, converting the whole message into `T`.
+    ///
+    /// Returns an error of kind `InvalidData` if `T` doesn't accept the number of parts.
+    pub async fn recv_multipart_typed<T>(&self) -> io::Result<T>
+    where
+        T: FromMultipart,
+    {
+        let msgs = self.recv_multipart_msg().await?;
+        T::from_multipart(msgs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Send a request and receive the reply (REQ sockets).
+    ///
+    /// REQ sockets must strictly alternate between sending and receiving.
+    /// If this future is dropped after the request has been sent, the socket is
+    /// left waiting for the reply and fails the next send with EFSM. Set ZMQ_REQ_RELAXED
+    /// (and ZMQ_REQ_CORRELATE) on sockets whose requests can be cancelled.
+    pub async fn request<T>(&self, frames: &[T]) -> io::Result<Vec<Vec<u8>>>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.send_multipart(frames).await?;
+        self.recv_multipart().await
+    }
+
+    /// Send a multi-part message, giving up after `dur`.
+    ///
+    /// Returns `Ok(None)` if the message couldn't be sent in time.
+    pub async fn send_timeout<T>(&self, msgs: &[T], dur: Duration) -> io::Result<Option<()>>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.timeout(
+            reactor::timeout(dur, self.send_multipart(msgs)),
+            Self::cancel_write,
+        )
+        .await
+    }
+
+    /// Send a multi-part message, waiting while the queues are at the high water mark
+    /// for up to `max_wait` (forever if `None`).
+    ///
+    /// Returns the message back as `Ok(Some(msgs))` if it couldn't be sent in time,
+    /// so that the caller can decide to drop or retry it.
+    pub async fn send_multipart_backpressure<T>(
+        &self,
+        msgs: Vec<T>,
+        max_wait: Option<Duration>,
+    ) -> io::Result<Option<Vec<T>>>
+    where
+        T: AsRef<[u8]>,
+    {
+        match max_wait {
+            Some(dur) => match self.send_timeout(&msgs, dur).await? {
+                Some(()) => Ok(None),
+                None => Ok(Some(msgs)),
+            },
+            None => self.send_multipart(&msgs).await.map(|_| None),
+        }
+    }
+
+    /// Receive a multi-part message, giving up after `dur`.
+    ///
+    /// Returns `Ok(None)` if no message arrived in time.
+    pub async fn recv_timeout(&self, dur: Duration) -> io::Result<Option<Vec<Vec<u8>>>> {
+        self.timeout(
+            reactor::timeout(dur, self.recv_multipart()),
+            Self::cancel_read,
+        )
+        .await
+    }
+
+    /// Receive a multi-part message, giving up at `deadline`.
+    ///
+    /// Returns `Ok(None)` if no message arrived in time. Unlike
+    /// [`recv_timeout`](Self::recv_timeout), the same deadline can be shared by
+    /// the steps of an exchange which must be over by then.
+    pub async fn recv_deadline(&self, deadline: Instant) -> io::Result<Option<Vec<Vec<u8>>>> {
+        self.timeout(
+            reactor::timeout_at(deadline, self.recv_multipart()),
+            Self::cancel_read,
+        )
+        .await
+    }
+
+    /// Wait until the socket becomes readable, without receiving anything.
+    ///
+    /// This is only a hint: the message can be taken by someone else in the meantime,
+    /// so a following [`try_recv_multipart`](Self::try_recv_multipart) may still
+    /// fail with `WouldBlock`.
+    pub async fn readable(&self) -> io::Result<()> {
+        poll_fn(|cx| self.poll_read(cx, |_| Ok(()))).await
+    }
+
+    /// Wait until the socket becomes writable, without sending anything.
+    ///
+    /// This is only a hint, as with [`readable`](Self::readable); a following
+    /// [`try_send_multipart`](Self::try_send_multipart) may still fail with `WouldBlock`.
+    pub async fn writable(&self) -> io::Result<()> {
+        poll_fn(|cx| self.poll_write(cx, |_| Ok(()))).await
+    }
+
+    /// Poll to receive a multi-part message.
+    ///
+    /// This is the building block of [`recv_multipart`](Self::recv_multipart) for
+    /// hand-written futures and streams. On `Poll::Pending`, the task of `cx` is woken up
+    /// when the socket may have become readable.
+    pub fn poll_recv_multipart(&self, cx: &mut task::Context) -> Poll<io::Result<Vec<Vec<u8>>>> {
+        self.poll_read(cx, |sock| sock.recv_multipart(zmq::DONTWAIT))
+    }
+
+    /// Poll to send a multi-part message.
+    ///
+    /// The message is sent as a whole once this returns `Poll::Ready(Ok(()))`.
+    /// On `Poll::Pending` nothing has been sent, and the task of `cx` is woken up
+    /// when the socket may have become writable.
+    pub fn poll_send_multipart<T>(&self, cx: &mut task::Context, msgs: &[T]) -> Poll<io::Result<()>>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.poll_write(cx, |sock| {
+            sock.send_multipart(msgs.iter().map(|m| m.as_ref()), zmq::DONTWAIT)
+                .map(|_| stats::len(msgs))
+        })
+        .map_ok(|_| ())
+    }
+
+    /// Check whether the socket can accept a message right now.
+    ///
+    /// A send waits for as long as this is `false`. For most socket types that's either
+    /// backpressure, where the queue of every peer is at the send high water mark (SNDHWM)
+    /// until the peers drain it, or no peer being connected at all. ZMQ_EVENTS doesn't tell
+    /// the two apart; [`monitor`](Self::monitor) reports the connections to do so.
+    /// See [`events`](Self::events) for the wakeup it may make.
+    pub fn is_writable_now(&self) -> io::Result<bool> {
+        self.events().map(|e| e.contains(zmq::POLLOUT))
+    }
+
+    /// Get the current value of ZMQ_EVENTS, i.e. whether the socket is readable
+    /// (`POLLIN`) and writable (`POLLOUT`) right now.
+    ///
+    /// This never waits nor registers the current task for wakeup. Reading the events
+    /// resets the edge-triggered fd though, so the tasks waiting for a direction
+    /// which turns out to be ready are woken up.
+    pub fn events(&self) -> io::Result<zmq::PollEvents> {
+        let events = self.inner.sock.get_events().map_err(|e| self.error(e))?;
+        self.inner.events.set(Some(events));
+        self.cross_wake(events);
+        Ok(events)
+    }
+
+    /// Check whether a message can be received right now, without waiting.
+    ///
+    /// libzmq can't peek at a message, so how many frames it has is only known
+    /// once it's received. See [`events`](Self::events) for the wakeup it may make.
+    pub fn has_message(&self) -> io::Result<bool> {
+        Ok(self.events()?.contains(zmq::POLLIN))
+    }
+
+    /// Try to send a multi-part message without waiting.
+    ///
+    /// Returns an error of kind `WouldBlock` if the socket can't accept the message
+    /// right now. This never registers the current task for wakeup, so it's safe
+    /// to call outside of a task context.
+    pub fn try_send_multipart<T>(&self, msgs: &[T]) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        let events = self.get_events(zmq::POLLOUT).map_err(|e| self.error(e))?;
+
+        if events.intersects(zmq::POLLOUT) {
+            self.inner.events.set(None);
+            self.inner
+                .sock
+                .send_multipart(msgs.iter().map(|m| m.as_ref()), zmq::DONTWAIT)
+                .map_err(|e| self.error(e))?;
+            self.inner.blocked.set(false);
+            self.inner.stats.sent(&stats::len(msgs));
+            Ok(())
+        } else {
+            self.inner.blocked.set(true);
+            Err(io::ErrorKind::WouldBlock.into())
+        }
+    }
+
+    /// Try to receive a multi-part message without waiting.
+    ///
+    /// Returns an error of kind `WouldBlock` if no message is available right now.
+    /// This never registers the current task for wakeup, so it's safe to call
+    /// outside of a task context.
+    pub fn try_recv_multipart(&self) -> io::Result<Vec<Vec<u8>>> {
+        let events = self.get_events(zmq::POLLIN).map_err(|e| self.error(e))?;
+
+        if events.intersects(zmq::POLLIN) {
+            self.inner.events.set(None);
+            let msgs = self
+                .inner
+                .sock
+                .recv_multipart(zmq::DONTWAIT)
+                .map_err(|e| self.error(e))?;
+            self.inner.stats.received(&msgs);
+            Ok(msgs)
+        } else {
+            Err(io::ErrorKind::WouldBlock.into())
+        }
+    }
+
+    /// Discard all the messages which can be received right now.
+    ///
+    /// Returns the number of messages discarded, which are not counted in the stats.
+    /// As with [`try_recv_multipart`](Self::try_recv_multipart), this never waits
+    /// nor registers the current task for wakeup.
+    pub fn drain(&self) -> io::Result<usize> {
+        self.inner.events.set(None);
+
+        let mut count = 0;
+        loop {
+            match self.inner.sock.recv_msg(zmq::DONTWAIT) {
+                Ok(msg) => {
+                    if msg.get_more() {
+                        discard_rest(&self.inner.sock).map_err(|e| self.error(e))?;
+                    }
+                    count += 1;
+                }
+                Err(zmq::Error::EAGAIN) => break,
+                Err(e) => return Err(self.error(e)),
+            }
+        }
+
+        // Receiving may have made the socket writable without an event on the fd.
+        let events = self.inner.sock.get_events().map_err(|e| self.error(e))?;
+        self.cross_wake(events & zmq::POLLOUT);
+        Ok(count)
+    }
+
+    /// Wait until the messages queued by the sends have drained as far as it can be told.
+    ///
+    /// A successful send only queues the message in libzmq, which hands it to the
+    /// transport in the background. libzmq doesn't report the length of the queue, so
+    /// if a send has been held back since the socket last accepted one, this waits until
+    /// the socket accepts messages again, i.e. the queue is below the high water mark;
+    /// up to that many messages can still be queued. Otherwise it returns right away.
+    /// Nothing tells whether a message has made it to the wire, let alone to a peer;
+    /// use replies from the peer for that.
+    ///
+    /// A send is also held back if the socket has no peer, e.g. a PUSH or DEALER whose
+    /// peers have gone, in which case this waits until one connects. Returns an error of
+    /// kind `Unsupported` right away for REQ and REP sockets, which accept a message
+    /// depending on the request-reply turn rather than the queue, and for PULL and SUB
+    /// sockets, which can't send.
+    pub async fn flush(&self) -> io::Result<()> {
+        let kind = self
+            .inner
+            .sock
+            .get_socket_type()
+            .map_err(|e| self.error(e))?;
+        if let zmq::SocketType::REQ
+        | zmq::SocketType::REP
+        | zmq::SocketType::PULL
+        | zmq::SocketType::SUB = kind
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("a {:?} socket has no send queue to flush", kind),
+            ));
+        }
+
+        if self.inner.blocked.get() {
+            self.writable().await?;
+        }
+        Ok(())
+    }
+
+    /// Close the socket, waiting for the queued messages within LINGER.
+    ///
+    /// Dropping the socket closes it right away; libzmq keeps sending the queued messages
+    /// in the background for the LINGER period, and anything not sent by then is lost.
+    /// Nothing tells whether the messages have made it, and terminating the context
+    /// blocks until they're sent or LINGER expires.
+    ///
+    /// This instead [`flush`](Self::flush)es the socket first if a send has been held
+    /// back since it last accepted one, up to the LINGER period (up to
+    /// [`CLOSE_FLUSH_LIMIT`] if it's -1, not at all if it's 0). Messages below the high
+    /// water mark are still left to LINGER. Sockets nothing has been sent through, and
+    /// those [`flush`](Self::flush) doesn't support, close right away.
+    ///
+    /// If the socket is shared with clones, this only drops this handle after waiting;
+    /// the socket is closed when the last clone is dropped.
+    pub async fn close(self) -> io::Result<()> {
+        let linger = self.get_linger()?;
+
+        if linger != 0 && self.inner.blocked.get() && self.stats().sent_messages > 0 {
+            let dur = match linger {
+                ms if ms > 0 => Duration::from_millis(ms as u64),
+                _ => CLOSE_FLUSH_LIMIT,
+            };
+            let _ = reactor::timeout(dur, self.flush()).await;
+        }
+
+        if let Ok(Inner { reactor, sock, .. }) = Rc::try_unwrap(self.inner) {
+            reactor.deregister()?;
+            drop(sock);
+        }
+        Ok(())
+    }
+
+    /// Create a sink which sends multi-part messages through this socket.
+    pub fn outgoing(&self) -> Outgoing<'_> {
+        Outgoing::new(self)
+    }
+
+    /// Split the socket into the sending half and the receiving half.
+    ///
+    /// The halves can be driven by different tasks. ZMQ sockets are not thread-safe,
+    /// so the halves are `!Send` and both must stay on the same thread
+    /// (e.g. tasks spawned on a `tokio::task::LocalSet`).
+    pub fn split(self) -> (SendHalf, RecvHalf) {
+        split::split(self)
     }
 
     /// Check the socket readiness via ZMQ_EVENTS.
@@ -71,59 +998,426 @@ impl Socket {
     ///
     /// (From ZMQ_FD section in http://api.zeromq.org/4-1:zmq-getsockopt)
     ///
-    /// Wake up task which is waiting for read
+    /// Wake up tasks which are waiting for read
     fn wakeup_read(&self) {
-        self.read.borrow().as_ref().map(|w| w.wake_by_ref());
+        self.inner.read.wake();
     }
 
-    /// Wake up task which is waiting for write
+    /// Wake up tasks which are waiting for write
     fn wakeup_write(&self) {
-        self.write.borrow().as_ref().map(|w| w.wake_by_ref());
+        self.inner.write.wake();
     }
 
-    fn sleep_read(&self, cx: &Context) {
-        self.read.borrow_mut().replace(cx.waker().clone());
+    /// Make the waiting tasks re-check the state after connecting or binding.
+    fn topology_changed(&self) {
+        self.inner.events.set(None);
+        self.wakeup_read();
+        self.wakeup_write();
     }
 
-    fn sleep_write(&self, cx: &Context) {
-        self.write.borrow_mut().replace(cx.waker().clone());
+    /// Wake up the tasks waiting for the directions in `events`, which have been seen
+    /// ready on the socket, possibly consuming the edge of the fd.
+    ///
+    /// The woken tasks check the socket by themselves, so a direction is woken up
+    /// only once until a task sleeps on it again; any more would be spurious polls.
+    fn cross_wake(&self, events: zmq::PollEvents) {
+        let woken = self.inner.cross_woken.get();
+        if events.contains(zmq::POLLIN) && !woken.contains(zmq::POLLIN) {
+            self.wakeup_read();
+        }
+        if events.contains(zmq::POLLOUT) && !woken.contains(zmq::POLLOUT) {
+            self.wakeup_write();
+        }
+        self.inner.cross_woken.set(woken | events);
     }
 
-    fn poll_write(&self, cx: &mut Context, msg: &[&[u8]]) -> Poll<io::Result<()>> {
-        let events = self.sock.get_events()?;
+    /// Put the current task into sleep until the socket becomes readable
+    ///
+    /// The reactor only keeps a single waker, so it is given the one
+    /// which wakes up all the tasks waiting for read.
+    fn sleep_read(&self, cx: &task::Context) -> io::Result<()> {
+        self.inner
+            .cross_woken
+            .set(self.inner.cross_woken.get() & !zmq::POLLIN);
+        self.inner.read.register(cx.waker());
+        self.inner.reactor.sleep_read(&self.inner.read)
+    }
 
-        if events.intersects(zmq::POLLOUT) {
-            match self.sock.send_multipart(msg, zmq::DONTWAIT) {
-                Ok(_) => Poll::Ready(Ok(())),
-                Err(zmq::Error::EAGAIN) => unreachable!(),
-                Err(e) => Poll::Ready(Err(e.into())),
-            }
-        } else {
-            self.evented.clear_write_ready(cx)?;
-            if events.intersects(zmq::POLLIN) {
+    /// Put the current task into sleep until the socket becomes writable
+    fn sleep_write(&self, cx: &task::Context) -> io::Result<()> {
+        self.inner
+            .cross_woken
+            .set(self.inner.cross_woken.get() & !zmq::POLLOUT);
+        self.inner.write.register(cx.waker());
+        self.inner.reactor.sleep_write(&self.inner.write)
+    }
+
+    /// Forget the current task waiting for read, if any
+    fn cancel_read(&self, cx: &task::Context) {
+        self.inner.read.unregister(cx.waker());
+    }
+
+    /// Forget the current task waiting for write, if any
+    fn cancel_write(&self, cx: &task::Context) {
+        self.inner.write.unregister(cx.waker());
+    }
+
+    /// Convert a ZMQ error into an io error.
+    ///
+    /// ETERM means the context has been terminated and the socket never becomes
+    /// ready again, so all the waiting tasks are woken up to see it as well.
+    fn error(&self, e: zmq::Error) -> io::Error {
+        match e {
+            zmq::Error::ETERM => {
                 self.wakeup_read();
+                self.wakeup_write();
+                Error::Zmq(e).into()
             }
-            self.sleep_write(cx);
-            Poll::Pending
+            e => Error::Zmq(e).into(),
         }
     }
 
-    fn poll_read(&self, cx: &mut Context) -> Poll<io::Result<Vec<Vec<u8>>>> {
-        let events = self.sock.get_events()?;
+    /// Get ZMQ_EVENTS, reusing the last result if it already has `wanted`.
+    ///
+    /// The last result is kept only while no operation has been done on the socket.
+    /// Positive readiness can only be taken away by an operation (or by a peer going
+    /// away, which makes the operation fail with EAGAIN), so it's safe to skip
+    /// the syscall in that case. Negative readiness is always re-checked.
+    fn get_events(&self, wanted: zmq::PollEvents) -> zmq::Result<zmq::PollEvents> {
+        match self.inner.events.get() {
+            Some(events) if events.intersects(wanted) => Ok(events),
+            _ => self.inner.sock.get_events(),
+        }
+    }
 
-        if events.intersects(zmq::POLLIN) {
-            match self.sock.recv_multipart(zmq::DONTWAIT) {
-                Ok(msg) => Poll::Ready(Ok(msg)),
-                Err(zmq::Error::EAGAIN) => unreachable!(),
-                Err(e) => Poll::Ready(Err(e.into())),
+    /// Run the send operation `f` once the socket becomes writable,
+    /// giving up with `TimedOut` after the send timeout.
+    async fn send_with<F, T>(&self, mut f: F) -> io::Result<T>
+    where
+        F: FnMut(&zmq::Socket) -> zmq::Result<T>,
+        T: Transfer,
+    {
+        let send = poll_fn(|cx| self.poll_write(cx, &mut f));
+        match self.inner.sndtimeo.get() {
+            Some(dur) => self
+                .timeout(reactor::timeout(dur, send), Self::cancel_write)
+                .await?
+                .ok_or_else(|| io::Error::new(io::ErrorKind::TimedOut, "send timed out")),
+            None => send.await,
+        }
+    }
+
+    /// Run the receive operation `f` once the socket becomes readable,
+    /// giving up with `TimedOut` after the receive timeout.
+    async fn recv_with<F, T>(&self, mut f: F) -> io::Result<T>
+    where
+        F: FnMut(&zmq::Socket) -> zmq::Result<T>,
+        T: Transfer,
+    {
+        let recv = poll_fn(|cx| self.poll_read(cx, &mut f));
+        match self.inner.rcvtimeo.get() {
+            Some(dur) => self
+                .timeout(reactor::timeout(dur, recv), Self::cancel_read)
+                .await?
+                .ok_or_else(|| io::Error::new(io::ErrorKind::TimedOut, "receive timed out")),
+            None => recv.await,
+        }
+    }
+
+    /// Run `timed`, a future given up on the timeout of the reactor, calling `cancel`
+    /// to forget the current task if it doesn't complete in time.
+    async fn timeout<F, T>(
+        &self,
+        timed: F,
+        cancel: fn(&Self, &task::Context),
+    ) -> io::Result<Option<T>>
+    where
+        F: Future<Output = Option<io::Result<T>>>,
+    {
+        match timed.await {
+            Some(res) => res.map(Some),
+            None => {
+                poll_fn(|cx| {
+                    cancel(self, cx);
+                    Poll::Ready(())
+                })
+                .await;
+                Ok(None)
             }
-        } else {
-            self.evented.clear_read_ready(cx, Ready::readable())?;
-            if events.intersects(zmq::POLLOUT) {
-                self.wakeup_write();
+        }
+    }
+
+    /// Run the send operation `f` once the socket becomes writable.
+    ///
+    /// The result of `f` is counted in the stats of the sent messages.
+    fn poll_write<F, T>(&self, cx: &mut task::Context, f: F) -> Poll<io::Result<T>>
+    where
+        F: FnMut(&zmq::Socket) -> zmq::Result<T>,
+        T: Transfer,
+    {
+        let events = match self.try_run(zmq::POLLOUT, f)? {
+            Ok(res) => {
+                self.inner.blocked.set(false);
+                self.inner.stats.sent(&res);
+                return Poll::Ready(Ok(res));
+            }
+            Err(events) => events,
+        };
+        self.inner.events.set(Some(events));
+        self.inner.blocked.set(true);
+
+        self.sleep_write(cx)?;
+        self.cross_wake(events & zmq::POLLIN);
+        Poll::Pending
+    }
+
+    /// Run the operation `f` if the socket is ready for `wanted`, re-checking ZMQ_EVENTS
+    /// up to the spin budget while it isn't.
+    ///
+    /// Returns the events to sleep on if `f` hasn't completed.
+    fn try_run<F, T>(
+        &self,
+        wanted: zmq::PollEvents,
+        mut f: F,
+    ) -> io::Result<Result<T, zmq::PollEvents>>
+    where
+        F: FnMut(&zmq::Socket) -> zmq::Result<T>,
+    {
+        let mut events = self.get_events(wanted).map_err(|e| self.error(e))?;
+        let mut spins = self.inner.spin.get();
+        let mut retried = false;
+
+        loop {
+            if events.intersects(wanted) {
+                self.inner.events.set(None);
+                // The readiness can be gone by the time the operation runs
+                // (e.g. drained by another thread), so EAGAIN just means to wait.
+                match self.run(&mut f) {
+                    Err(zmq::Error::EAGAIN) => {}
+                    Ok(res) => return Ok(Ok(res)),
+                    Err(e) => return Err(self.error(e)),
+                }
+                // The fd is edge-triggered, so sleep only on a fresh state.
+                events = self.inner.sock.get_events().map_err(|e| self.error(e))?;
+                // Reading it has consumed the edge, so no wakeup comes for a readiness
+                // it shows: run the operation again instead of sleeping on it.
+                if events.intersects(wanted) && !retried {
+                    retried = true;
+                    continue;
+                }
             }
-            self.sleep_read(cx);
-            Poll::Pending
+            if spins == 0 {
+                return Ok(Err(events));
+            }
+            spins -= 1;
+            // Let a peer on the same CPU run, or the state can't change while spinning.
+            std::thread::yield_now();
+            events = self.inner.sock.get_events().map_err(|e| self.error(e))?;
         }
     }
+
+    /// Run the operation `f`, retrying once if it's interrupted by a signal.
+    fn run<F, T>(&self, mut f: F) -> zmq::Result<T>
+    where
+        F: FnMut(&zmq::Socket) -> zmq::Result<T>,
+    {
+        match f(&self.inner.sock) {
+            Err(zmq::Error::EINTR) => f(&self.inner.sock),
+            res => res,
+        }
+    }
+
+    /// Run the receive operation `f` once the socket becomes readable.
+    ///
+    /// The result of `f` is counted in the stats of the received messages.
+    fn poll_read<F, T>(&self, cx: &mut task::Context, f: F) -> Poll<io::Result<T>>
+    where
+        F: FnMut(&zmq::Socket) -> zmq::Result<T>,
+        T: Transfer,
+    {
+        let events = match self.try_run(zmq::POLLIN, f)? {
+            Ok(res) => {
+                self.inner.stats.received(&res);
+                return Poll::Ready(Ok(res));
+            }
+            Err(events) => events,
+        };
+        self.inner.events.set(Some(events));
+
+        self.sleep_read(cx)?;
+        self.cross_wake(events & zmq::POLLOUT);
+        Poll::Pending
+    }
+}
+
+/// Shows the socket type, the last endpoint and whether tasks are waiting to
+/// read or write. Whatever can't be queried from libzmq is shown as `?` or `None`.
+impl fmt::Debug for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.inner.sock.get_socket_type() {
+            Ok(kind) => format!("{:?}", kind),
+            Err(_) => "?".to_owned(),
+        };
+        let endpoint = self.last_endpoint().ok().filter(|e| !e.is_empty());
+
+        f.debug_struct("Socket")
+            .field("type", &format_args!("{}", kind))
+            .field("endpoint", &endpoint)
+            .field("read_waiting", &self.inner.read.is_waiting())
+            .field("write_waiting", &self.inner.write.is_waiting())
+            .finish()
+    }
+}
+
+/// The ZMQ_FD of the socket, to fold its readiness into another event loop
+/// (e.g. with `mio::unix::SourceFd`).
+///
+/// The fd stays owned by the socket and registered to the reactor of this crate.
+/// It's edge-triggered and only tells that ZMQ_EVENTS may have changed: check
+/// [`Socket::events`] when it fires and after every send and receive, since they
+/// may consume the readiness without another edge.
+#[cfg(unix)]
+impl std::os::unix::io::AsRawFd for Socket {
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        self.inner.fd
+    }
+}
+
+/// Unbind the raw socket `raw` from `endpoint`, which the `zmq` crate doesn't wrap.
+fn unbind(raw: *mut c_void, endpoint: &str) -> zmq::Result<()> {
+    let endpoint = CString::new(endpoint).map_err(|_| zmq::Error::EINVAL)?;
+    // SAFETY: `raw` is the live socket of the caller and `endpoint` is a valid C string
+    // for the duration of the call.
+    if unsafe { zmq_sys::zmq_unbind(raw, endpoint.as_ptr()) } == -1 {
+        return Err(zmq::Error::from_raw(unsafe { zmq_sys::zmq_errno() }));
+    }
+    Ok(())
+}
+
+/// Send `data` to the raw socket `raw` in a message which references it.
+fn send_const(raw: *mut c_void, data: &'static [u8], flags: i32) -> zmq::Result<()> {
+    let mut msg = zmq_sys::zmq_msg_t { __: [0; 64] };
+    // SAFETY: The message only borrows `data`, which is static, so it needs no
+    // deallocation function. libzmq takes the message over if it's sent, and
+    // it's closed here otherwise.
+    unsafe {
+        let rc = zmq_sys::zmq_msg_init_data(
+            &mut msg,
+            data.as_ptr() as *mut c_void,
+            data.len(),
+            None,
+            std::ptr::null_mut(),
+        );
+        if rc == -1 {
+            return Err(zmq::Error::from_raw(zmq_sys::zmq_errno()));
+        }
+        if zmq_sys::zmq_msg_send(&mut msg, raw, flags) == -1 {
+            let e = zmq::Error::from_raw(zmq_sys::zmq_errno());
+            zmq_sys::zmq_msg_close(&mut msg);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Receive all the parts of a message without copying.
+fn recv_msgs(sock: &zmq::Socket) -> zmq::Result<Vec<zmq::Message>> {
+    let mut msgs = vec![];
+    loop {
+        let msg = sock.recv_msg(zmq::DONTWAIT)?;
+        let more = msg.get_more();
+        msgs.push(msg);
+        if !more {
+            return Ok(msgs);
+        }
+    }
+}
+
+/// Receive all the parts of a message, telling whether RCVMORE cleared at the end.
+fn recv_verbose(sock: &zmq::Socket) -> zmq::Result<(Vec<Vec<u8>>, bool)> {
+    let mut frames = vec![sock.recv_bytes(zmq::DONTWAIT)?];
+    loop {
+        match sock.get_rcvmore() {
+            Ok(false) => return Ok((frames, true)),
+            Ok(true) => {}
+            Err(_) => return Ok((frames, false)),
+        }
+        match sock.recv_bytes(zmq::DONTWAIT) {
+            Ok(frame) => frames.push(frame),
+            Err(_) => return Ok((frames, false)),
+        }
+    }
+}
+
+/// Receive all the parts of a message, calling `f` with each of them.
+///
+/// Returns the size of the message.
+fn recv_each<F>(sock: &zmq::Socket, f: &mut F) -> zmq::Result<usize>
+where
+    F: FnMut(&[u8], bool),
+{
+    let mut msg = zmq::Message::new();
+    sock.recv(&mut msg, zmq::DONTWAIT)?;
+    let mut n = 0;
+    loop {
+        let more = msg.get_more();
+        f(&msg, !more);
+        n += msg.len();
+        if !more {
+            return Ok(n);
+        }
+        if let Err(e) = sock.recv(&mut msg, zmq::DONTWAIT) {
+            let _ = discard_rest(sock);
+            return Err(e);
+        }
+    }
+}
+
+/// Receive all the parts of a message into `buf`, reusing the frames in it.
+///
+/// Returns the size of the message.
+fn recv_into(sock: &zmq::Socket, buf: &mut Vec<Vec<u8>>) -> zmq::Result<usize> {
+    let mut n = 0;
+    loop {
+        let msg = sock.recv_msg(zmq::DONTWAIT)?;
+        match buf.get_mut(n) {
+            Some(frame) => {
+                frame.clear();
+                frame.extend_from_slice(&msg);
+            }
+            None => buf.push(msg.to_vec()),
+        }
+        n += 1;
+        if !msg.get_more() {
+            buf.truncate(n);
+            return Ok(stats::len(buf));
+        }
+    }
+}
+
+/// Receive all the parts of a message into the `zmq::Message`s in `buf`.
+///
+/// Returns the size of the message.
+fn recv_msgs_into(sock: &zmq::Socket, buf: &mut Vec<zmq::Message>) -> zmq::Result<usize> {
+    let mut n = 0;
+    loop {
+        if n == buf.len() {
+            buf.push(zmq::Message::new());
+        }
+        let msg = &mut buf[n];
+        sock.recv(msg, zmq::DONTWAIT)?;
+        n += 1;
+        if !msg.get_more() {
+            buf.truncate(n);
+            return Ok(buf.iter().map(|m| m.len()).sum());
+        }
+    }
+}
+
+/// Discard the remaining parts of the message being received.
+fn discard_rest(sock: &zmq::Socket) -> zmq::Result<()> {
+    while sock.get_rcvmore()? {
+        sock.recv_msg(zmq::DONTWAIT)?;
+    }
+    Ok(())
 }
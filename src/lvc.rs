@@ -0,0 +1,78 @@
+use crate::{Socket, Subscription};
+use std::{cell::RefCell, collections::HashMap, io};
+
+/// A last value cache on an XPUB socket, for subscribers joining late.
+///
+/// The last message published on each topic is kept, and sent again when
+/// a subscription to the topic (or to a prefix of it) arrives. ZMQ_XPUB_VERBOSE is
+/// turned on so that every subscription arrives, not only the first one of each topic.
+/// XPUB can't send to a single subscriber, so the replayed messages also reach
+/// the other subscribers of the topic, which see them twice.
+pub struct LastValueCache {
+    sock: Socket,
+    values: RefCell<HashMap<Vec<u8>, Vec<Vec<u8>>>>,
+}
+
+impl LastValueCache {
+    /// Keep the last values published through the XPUB socket `sock`.
+    pub fn new(sock: Socket) -> io::Result<Self> {
+        sock.socket().set_xpub_verbose(true)?;
+        Ok(Self {
+            sock,
+            values: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Publish a message of `topic` followed by the `body` parts, keeping it for replay.
+    pub async fn publish<T>(&self, topic: &[u8], body: &[T]) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut msgs = Vec::with_capacity(body.len() + 1);
+        msgs.push(topic.to_vec());
+        msgs.extend(body.iter().map(|part| part.as_ref().to_vec()));
+
+        self.sock.send_multipart(&msgs).await?;
+        self.values.borrow_mut().insert(msgs.remove(0), msgs);
+        Ok(())
+    }
+
+    /// Receive a subscription change, replaying the last values of the topics it covers
+    /// if it's a subscription.
+    pub async fn recv_subscription(&self) -> io::Result<Subscription> {
+        let sub = self.sock.recv_subscription().await?;
+
+        if sub.subscribe {
+            let replay: Vec<_> = self
+                .values
+                .borrow()
+                .iter()
+                .filter(|(topic, _)| topic.starts_with(&sub.topic))
+                .map(|(topic, body)| {
+                    let mut msgs = vec![topic.clone()];
+                    msgs.extend(body.iter().cloned());
+                    msgs
+                })
+                .collect();
+            for msgs in replay {
+                self.sock.send_multipart(&msgs).await?;
+            }
+        }
+        Ok(sub)
+    }
+
+    /// The last value published on `topic`, without the topic.
+    pub fn get(&self, topic: &[u8]) -> Option<Vec<Vec<u8>>> {
+        self.values.borrow().get(topic).cloned()
+    }
+
+    /// The underlying socket.
+    pub fn get_ref(&self) -> &Socket {
+        &self.sock
+    }
+
+    /// Take the underlying socket out.
+    pub fn into_inner(self) -> Socket {
+        self.sock
+    }
+}
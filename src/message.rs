@@ -0,0 +1,110 @@
+use crate::{Envelope, Socket};
+use std::io;
+
+/// Builder of a multi-part message made of owned, borrowed and static frames,
+/// sent by [`Socket::send_built`].
+///
+/// Building doesn't allocate until the first frame is added, and the builder
+/// is only borrowed when sent, so it can be sent again or [`clear`](Self::clear)ed
+/// to keep its allocation.
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder<'a> {
+    frames: Vec<Frame<'a>>,
+}
+
+#[derive(Debug, Clone)]
+enum Frame<'a> {
+    Owned(Vec<u8>),
+    Borrowed(&'a [u8]),
+    Const(&'static [u8]),
+}
+
+impl<'a> MessageBuilder<'a> {
+    /// Start building an empty message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a frame borrowing `data`.
+    pub fn frame(mut self, data: &'a [u8]) -> Self {
+        self.frames.push(Frame::Borrowed(data));
+        self
+    }
+
+    /// Add a frame owning `data`.
+    pub fn owned(mut self, data: Vec<u8>) -> Self {
+        self.frames.push(Frame::Owned(data));
+        self
+    }
+
+    /// Add a frame of static data, sent without copying as with [`Socket::send_const`].
+    pub fn constant(mut self, data: &'static [u8]) -> Self {
+        self.frames.push(Frame::Const(data));
+        self
+    }
+
+    /// Add an empty frame, e.g. the delimiter between the routing frames and the payload.
+    pub fn empty(self) -> Self {
+        self.constant(b"")
+    }
+
+    /// Add the routing frames of `envelope`, to reply to the peer it has been received from.
+    pub fn routing(mut self, envelope: &'a Envelope) -> Self {
+        self.frames.extend(
+            envelope
+                .routing()
+                .iter()
+                .map(|f| Frame::Borrowed(f.as_slice())),
+        );
+        self
+    }
+
+    /// The number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether there's no frame.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Remove all the frames, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+impl Socket {
+    /// Send the message built by `msg`.
+    ///
+    /// The owned and borrowed frames are copied into the parts by libzmq, the static ones
+    /// aren't. Each part is sent with SNDMORE except the last one, and a message stopped
+    /// halfway is resumed as with [`send_all`](Self::send_all). Nothing is sent if
+    /// there's no frame.
+    pub async fn send_built(&self, msg: &MessageBuilder<'_>) -> io::Result<()> {
+        let raw = self.inner.raw;
+        let mut at = 0;
+        let mut sent = 0;
+
+        self.send_with(|sock| {
+            while let Some(frame) = msg.frames.get(at) {
+                let flags = if at + 1 < msg.frames.len() {
+                    zmq::DONTWAIT | zmq::SNDMORE
+                } else {
+                    zmq::DONTWAIT
+                };
+                let len = match frame {
+                    Frame::Owned(data) => sock.send(&data[..], flags).map(|_| data.len()),
+                    Frame::Borrowed(data) => sock.send(*data, flags).map(|_| data.len()),
+                    Frame::Const(data) => crate::send_const(raw, data, flags).map(|_| data.len()),
+                }?;
+                sent += len;
+                at += 1;
+            }
+            Ok(sent)
+        })
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+use crate::Socket;
+use futures::{future::poll_fn, ready, Stream, StreamExt};
+use log::*;
+use std::{
+    io,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+static MONITOR_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The events which can be reported by a monitor.
+const EVENTS: &[zmq::SocketEvent] = &[
+    zmq::SocketEvent::CONNECTED,
+    zmq::SocketEvent::CONNECT_DELAYED,
+    zmq::SocketEvent::CONNECT_RETRIED,
+    zmq::SocketEvent::LISTENING,
+    zmq::SocketEvent::BIND_FAILED,
+    zmq::SocketEvent::ACCEPTED,
+    zmq::SocketEvent::ACCEPT_FAILED,
+    zmq::SocketEvent::CLOSED,
+    zmq::SocketEvent::CLOSE_FAILED,
+    zmq::SocketEvent::DISCONNECTED,
+    zmq::SocketEvent::MONITOR_STOPPED,
+    zmq::SocketEvent::HANDSHAKE_FAILED_NO_DETAIL,
+    zmq::SocketEvent::HANDSHAKE_SUCCEEDED,
+    zmq::SocketEvent::HANDSHAKE_FAILED_PROTOCOL,
+    zmq::SocketEvent::HANDSHAKE_FAILED_AUTH,
+];
+
+/// A stream of socket lifecycle events, created by [`Socket::monitor`].
+///
+/// Each item is the event and the endpoint it's about.
+/// The stream ends when the monitor is stopped.
+pub struct MonitorStream {
+    sock: Socket,
+    stopped: bool,
+}
+
+impl Socket {
+    /// Start monitoring the lifecycle events of this socket.
+    ///
+    /// `events` selects the events to report (`SocketEvent::ALL` for everything).
+    /// The events are delivered through an inproc PAIR socket created in `ctx`,
+    /// which must be the context this socket was created in.
+    pub async fn monitor(
+        &self,
+        ctx: &zmq::Context,
+        events: zmq::SocketEvent,
+    ) -> io::Result<MonitorStream> {
+        let endpoint = format!(
+            "inproc://zmq-async-monitor-{}",
+            MONITOR_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        self.inner
+            .sock
+            .monitor(&endpoint, events.to_raw() as i32)
+            .map_err(|e| self.error(e))?;
+
+        let sock = ctx.socket(zmq::SocketType::PAIR)?;
+        sock.connect(&endpoint)?;
+
+        Ok(MonitorStream {
+            sock: Socket::new(sock).await?,
+            stopped: false,
+        })
+    }
+
+    /// Stop the monitor running on this socket, if any.
+    fn stop_monitor(&self) -> io::Result<()> {
+        // SAFETY: `raw` is the live socket, and a null endpoint stops its monitor.
+        if unsafe { zmq_sys::zmq_socket_monitor(self.inner.raw, std::ptr::null(), 0) } == -1 {
+            let e = zmq::Error::from_raw(unsafe { zmq_sys::zmq_errno() });
+            return Err(self.error(e));
+        }
+        Ok(())
+    }
+
+    /// Wait until a peer has completed the handshake with this socket.
+    ///
+    /// This avoids the "slow joiner" problem of PUB sockets, which drop the messages sent
+    /// before the subscribers are connected. Only the handshakes which happen after the call
+    /// are seen, so call it right after binding, or before the peers start connecting.
+    /// It's built on [`monitor`](Self::monitor) and replaces any monitor already running on
+    /// this socket, stopping it before returning. Inproc connections report no events, so
+    /// this always times out on them.
+    ///
+    /// Returns an error of kind `TimedOut` if no peer connected within `timeout`.
+    pub async fn wait_connected(&self, ctx: &zmq::Context, timeout: Duration) -> io::Result<()> {
+        let mut events = self
+            .monitor(ctx, zmq::SocketEvent::HANDSHAKE_SUCCEEDED)
+            .await?;
+
+        let res = match crate::reactor::timeout(timeout, events.next()).await {
+            Some(Some(res)) => res.map(|_| ()),
+            Some(None) => Err(io::Error::other(
+                "the monitor stopped before a peer connected",
+            )),
+            None => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no peer connected in time",
+            )),
+        };
+        let stopped = self.stop_monitor();
+        res.and(stopped)
+    }
+
+    /// Wait until `n` peers are connected to this socket.
+    ///
+    /// The connections are counted from the CONNECTED and ACCEPTED events, minus
+    /// the DISCONNECTED events, of a [`monitor`](Self::monitor) which replaces any
+    /// monitor already running on this socket and is stopped before returning. As with
+    /// [`wait_connected`](Self::wait_connected), only the connections made after the call
+    /// are counted, and inproc connections are never counted.
+    ///
+    /// Returns an error of kind `TimedOut`, telling how many peers did connect,
+    /// if there aren't `n` of them within `timeout`.
+    pub async fn wait_for_peers(
+        &self,
+        ctx: &zmq::Context,
+        n: usize,
+        timeout: Duration,
+    ) -> io::Result<()> {
+        let mut events = self.monitor(ctx, zmq::SocketEvent::ALL).await?;
+
+        let mut peers = 0;
+        let count = async {
+            while peers < n {
+                match events.next().await {
+                    Some(Ok((zmq::SocketEvent::CONNECTED, _)))
+                    | Some(Ok((zmq::SocketEvent::ACCEPTED, _))) => peers += 1,
+                    Some(Ok((zmq::SocketEvent::DISCONNECTED, _))) => {
+                        peers = peers.saturating_sub(1)
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e),
+                    None => {
+                        return Err(io::Error::other(
+                            "the monitor stopped before the peers connected",
+                        ))
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        let res = match crate::reactor::timeout(timeout, count).await {
+            Some(res) => res,
+            None => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("only {} of {} peers connected in time", peers, n),
+            )),
+        };
+        let stopped = self.stop_monitor();
+        res.and(stopped)
+    }
+
+    /// Wait until a ZMTP handshake of this socket completes, failing early if it fails.
+    ///
+    /// This turns the CURVE, PLAIN or ZAP failures, which otherwise only show as
+    /// messages never arriving, into errors telling what went wrong:
+    /// an error of kind `PermissionDenied` with the ZAP status code if the peer was
+    /// denied, or of kind `InvalidData` describing the protocol error (e.g.
+    /// a cryptographic error or a mechanism mismatch) otherwise. As with
+    /// [`wait_connected`](Self::wait_connected), only the handshakes which happen
+    /// after the call are seen, and this replaces any monitor running on this socket,
+    /// stopping it before returning.
+    ///
+    /// Returns an error of kind `TimedOut` if no handshake completed within `timeout`.
+    pub async fn await_handshake(&self, ctx: &zmq::Context, timeout: Duration) -> io::Result<()> {
+        let mut events = self.monitor(ctx, zmq::SocketEvent::ALL).await?;
+
+        let handshake = async {
+            loop {
+                let (event, value, endpoint) = match poll_fn(|cx| events.poll_event(cx)).await {
+                    Some(res) => res?,
+                    None => {
+                        return Err(io::Error::other(
+                            "the monitor stopped before a handshake completed",
+                        ))
+                    }
+                };
+                match event {
+                    zmq::SocketEvent::HANDSHAKE_SUCCEEDED => return Ok(()),
+                    zmq::SocketEvent::HANDSHAKE_FAILED_AUTH => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::PermissionDenied,
+                            format!(
+                                "authentication of {} failed with ZAP status {}",
+                                endpoint, value
+                            ),
+                        ))
+                    }
+                    zmq::SocketEvent::HANDSHAKE_FAILED_PROTOCOL => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "handshake with {} failed: {} ({:#x})",
+                                endpoint,
+                                protocol_error(value),
+                                value
+                            ),
+                        ))
+                    }
+                    zmq::SocketEvent::HANDSHAKE_FAILED_NO_DETAIL => {
+                        return Err(io::Error::other(format!(
+                            "handshake with {} failed",
+                            endpoint
+                        )))
+                    }
+                    _ => {}
+                }
+            }
+        };
+
+        let res = match crate::reactor::timeout(timeout, handshake).await {
+            Some(res) => res,
+            None => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no handshake completed in time",
+            )),
+        };
+        let stopped = self.stop_monitor();
+        res.and(stopped)
+    }
+}
+
+impl MonitorStream {
+    /// Poll for the next event, with its value and endpoint.
+    fn poll_event(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<Option<io::Result<(zmq::SocketEvent, u32, String)>>> {
+        while !self.stopped {
+            let msgs = match ready!(self.sock.poll_read(cx, crate::recv_msgs)) {
+                Ok(msgs) => msgs,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+
+            match parse(&msgs) {
+                Ok(Some((zmq::SocketEvent::MONITOR_STOPPED, _, _))) => self.stopped = true,
+                Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+                Ok(None) => {}
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+impl Stream for MonitorStream {
+    type Item = io::Result<(zmq::SocketEvent, String)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .poll_event(cx)
+            .map_ok(|(event, _, endpoint)| (event, endpoint))
+    }
+}
+
+/// Parse a monitor message: a frame with the 16-bit event number and
+/// a 32-bit value, followed by a frame with the endpoint.
+///
+/// Returns `None` for events unknown to the `zmq` crate.
+fn parse(msgs: &[zmq::Message]) -> io::Result<Option<(zmq::SocketEvent, u32, String)>> {
+    match msgs {
+        [event, endpoint] if event.len() == 6 => {
+            let raw = u16::from_ne_bytes([event[0], event[1]]);
+            let value = u32::from_ne_bytes([event[2], event[3], event[4], event[5]]);
+            let endpoint = String::from_utf8_lossy(endpoint).into_owned();
+
+            match EVENTS.iter().find(|e| e.to_raw() == raw) {
+                Some(event) => Ok(Some((*event, value, endpoint))),
+                None => {
+                    debug!("Ignore unknown monitor event {} on {}", raw, endpoint);
+                    Ok(None)
+                }
+            }
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "malformed monitor message",
+        )),
+    }
+}
+
+/// Describe the value of a HANDSHAKE_FAILED_PROTOCOL event.
+fn protocol_error(value: u32) -> &'static str {
+    match value {
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_UNEXPECTED_COMMAND => "unexpected command",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_INVALID_SEQUENCE => "invalid sequence",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_KEY_EXCHANGE => "key exchange error",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_UNSPECIFIED
+        | zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_MESSAGE
+        | zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_HELLO
+        | zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_INITIATE
+        | zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_ERROR
+        | zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_READY
+        | zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MALFORMED_COMMAND_WELCOME => "malformed command",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_INVALID_METADATA => "invalid metadata",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_CRYPTOGRAPHIC => "cryptographic error",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZMTP_MECHANISM_MISMATCH => "security mechanism mismatch",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZAP_MALFORMED_REPLY => "malformed ZAP reply",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZAP_BAD_REQUEST_ID => "bad ZAP request id",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZAP_BAD_VERSION => "bad ZAP version",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZAP_INVALID_STATUS_CODE => "invalid ZAP status code",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZAP_INVALID_METADATA => "invalid ZAP metadata",
+        zmq_sys::ZMQ_PROTOCOL_ERROR_ZAP_UNSPECIFIED => "ZAP error",
+        _ => "protocol error",
+    }
+}
@@ -0,0 +1,29 @@
+use crate::Socket;
+use serde::{de::DeserializeOwned, Serialize};
+use std::io;
+
+impl Socket {
+    /// Serialize `v` into MessagePack and send it as a single-part message.
+    ///
+    /// Structs are encoded as maps with the field names, so that the receiver
+    /// doesn't depend on the field order.
+    pub async fn send_msgpack<T>(&self, v: &T) -> io::Result<()>
+    where
+        T: Serialize,
+    {
+        let buf = rmp_serde::to_vec_named(v)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.send(buf).await
+    }
+
+    /// Receive a single-part message and deserialize it from MessagePack.
+    ///
+    /// Returns an error of kind `InvalidData` if the message can't be deserialized into `T`.
+    pub async fn recv_msgpack<T>(&self) -> io::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let msg = self.recv().await?;
+        rmp_serde::from_slice(&msg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
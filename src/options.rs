@@ -0,0 +1,360 @@
+use crate::Socket;
+use std::{convert::TryFrom, io, mem, os::raw::c_void, time::Duration};
+
+impl Socket {
+    /// Subscribe to messages starting with `topic` (SUB/XSUB sockets only).
+    pub fn subscribe<T>(&self, topic: T) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.inner
+            .sock
+            .set_subscribe(topic.as_ref())
+            .map_err(|e| self.error(e))
+    }
+
+    /// Remove a subscription added by [`subscribe`](Self::subscribe).
+    pub fn unsubscribe<T>(&self, topic: T) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.inner
+            .sock
+            .set_unsubscribe(topic.as_ref())
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set the value of LINGER socket option in milliseconds.
+    ///
+    /// See [`close`](Self::close) for how it affects closing the socket.
+    pub fn set_linger(&self, linger: i32) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_linger(linger)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of LINGER socket option in milliseconds.
+    pub fn get_linger(&self) -> io::Result<i32> {
+        self.inner.sock.get_linger().map_err(|e| self.error(e))
+    }
+
+    /// Get the endpoint the socket was last bound or connected to.
+    ///
+    /// Ports bound with a wildcard (e.g. `tcp://127.0.0.1:*`) are resolved to the actual port.
+    pub fn last_endpoint(&self) -> io::Result<String> {
+        self.inner
+            .sock
+            .get_last_endpoint()
+            .map_err(|e| self.error(e))?
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the last endpoint is not valid UTF-8",
+                )
+            })
+    }
+
+    /// Set the value of ROUTING_ID (a.k.a. IDENTITY) socket option.
+    ///
+    /// It only takes effect on the connections made after it's set.
+    pub fn set_identity(&self, id: &[u8]) -> io::Result<()> {
+        self.inner.sock.set_identity(id).map_err(|e| self.error(e))
+    }
+
+    /// Get the value of ROUTING_ID (a.k.a. IDENTITY) socket option.
+    pub fn identity(&self) -> io::Result<Vec<u8>> {
+        self.inner.sock.get_identity().map_err(|e| self.error(e))
+    }
+
+    /// Set the value of ROUTER_MANDATORY socket option (ROUTER sockets only).
+    ///
+    /// When it's on, sending a message to an identity which matches no connected peer
+    /// fails with EHOSTUNREACH, of kind `HostUnreachable`, instead of dropping the message.
+    /// libzmq also makes the socket writable only while a peer can take a message,
+    /// so sends wait as long as no peer is connected.
+    pub fn set_router_mandatory(&self, on: bool) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_router_mandatory(on)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set the value of CONFLATE socket option, to keep only the last message in the queues.
+    ///
+    /// libzmq only applies it to the connections made after it's set, so this returns
+    /// an error of kind `InvalidInput` once the socket has been bound or connected.
+    /// Conflated messages can't be multi-part.
+    pub fn set_conflate(&self, on: bool) -> io::Result<()> {
+        self.check_unconnected("CONFLATE")?;
+        self.inner.sock.set_conflate(on).map_err(|e| self.error(e))
+    }
+
+    /// Set the value of IMMEDIATE socket option, to queue messages only to
+    /// the connections which have completed.
+    ///
+    /// Sends then wait while no connection is up, instead of piling the messages up
+    /// for the peers not connected yet. As with [`set_conflate`](Self::set_conflate),
+    /// this returns an error of kind `InvalidInput` once the socket has been connected.
+    pub fn set_immediate(&self, on: bool) -> io::Result<()> {
+        self.check_unconnected("IMMEDIATE")?;
+        self.inner.sock.set_immediate(on).map_err(|e| self.error(e))
+    }
+
+    /// Set the value of MAXMSGSIZE socket option in bytes (`-1` for no limit).
+    ///
+    /// libzmq disconnects a peer which sends a larger message, so it's never received.
+    pub fn set_maxmsgsize(&self, bytes: i64) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_maxmsgsize(bytes)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of MAXMSGSIZE socket option in bytes.
+    pub fn get_maxmsgsize(&self) -> io::Result<i64> {
+        self.inner.sock.get_maxmsgsize().map_err(|e| self.error(e))
+    }
+
+    /// Set the value of BACKLOG socket option, the queue length of the pending
+    /// connections of the sockets bound after it's set.
+    pub fn set_backlog(&self, backlog: i32) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_backlog(backlog)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of BACKLOG socket option.
+    pub fn get_backlog(&self) -> io::Result<i32> {
+        self.inner.sock.get_backlog().map_err(|e| self.error(e))
+    }
+
+    /// Set the value of TCP_KEEPALIVE socket option (`1` to enable SO_KEEPALIVE,
+    /// `0` to disable it, `-1` to leave the OS default).
+    ///
+    /// This and the other `tcp_keepalive` options only apply to the TCP connections
+    /// made after they're set.
+    pub fn set_tcp_keepalive(&self, keepalive: i32) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_tcp_keepalive(keepalive)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of TCP_KEEPALIVE socket option.
+    pub fn get_tcp_keepalive(&self) -> io::Result<i32> {
+        self.inner
+            .sock
+            .get_tcp_keepalive()
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set the value of TCP_KEEPALIVE_IDLE socket option in seconds
+    /// (`-1` for the OS default).
+    pub fn set_tcp_keepalive_idle(&self, secs: i32) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_tcp_keepalive_idle(secs)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of TCP_KEEPALIVE_IDLE socket option in seconds.
+    pub fn get_tcp_keepalive_idle(&self) -> io::Result<i32> {
+        self.inner
+            .sock
+            .get_tcp_keepalive_idle()
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set the value of TCP_KEEPALIVE_CNT socket option, the number of unanswered
+    /// probes before the connection is dropped (`-1` for the OS default).
+    pub fn set_tcp_keepalive_cnt(&self, cnt: i32) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_tcp_keepalive_cnt(cnt)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of TCP_KEEPALIVE_CNT socket option.
+    pub fn get_tcp_keepalive_cnt(&self) -> io::Result<i32> {
+        self.inner
+            .sock
+            .get_tcp_keepalive_cnt()
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set the value of TCP_KEEPALIVE_INTVL socket option in seconds, the interval
+    /// between the probes (`-1` for the OS default).
+    pub fn set_tcp_keepalive_intvl(&self, secs: i32) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_tcp_keepalive_intvl(secs)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Get the value of TCP_KEEPALIVE_INTVL socket option in seconds.
+    pub fn get_tcp_keepalive_intvl(&self) -> io::Result<i32> {
+        self.inner
+            .sock
+            .get_tcp_keepalive_intvl()
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set how long the async receives wait for a message (forever if `None`).
+    ///
+    /// A receive which times out fails with an error of kind `TimedOut`. This is kept
+    /// by the async socket and applies to the `recv*` methods, not to the `poll_*`
+    /// methods and streams. RCVTIMEO of libzmq never applies, since the socket is only
+    /// ever used without blocking.
+    pub fn set_rcvtimeo(&self, timeout: Option<Duration>) {
+        self.inner.rcvtimeo.set(timeout);
+    }
+
+    /// Get how long the async receives wait for a message.
+    pub fn rcvtimeo(&self) -> Option<Duration> {
+        self.inner.rcvtimeo.get()
+    }
+
+    /// Set how long the async sends wait for the socket to accept a message
+    /// (forever if `None`).
+    ///
+    /// This is the sending counterpart of [`set_rcvtimeo`](Self::set_rcvtimeo)
+    /// and applies to the `send*` methods.
+    pub fn set_sndtimeo(&self, timeout: Option<Duration>) {
+        self.inner.sndtimeo.set(timeout);
+    }
+
+    /// Get how long the async sends wait for the socket to accept a message.
+    pub fn sndtimeo(&self) -> Option<Duration> {
+        self.inner.sndtimeo.get()
+    }
+
+    /// Set how many times the async operations re-check ZMQ_EVENTS before parking the task
+    /// (`0`, the default, to park right away).
+    ///
+    /// This is meant for inproc sockets, whose peers run on other threads of the process:
+    /// a message may arrive within a few checks, saving the round trip through
+    /// the reactor. The thread is yielded to the OS between the checks, so that a peer
+    /// on the same CPU can run, but the other tasks on the thread are blocked meanwhile;
+    /// measure before raising it. It applies whatever the transport, as it's kept by
+    /// the async socket like [`set_rcvtimeo`](Self::set_rcvtimeo).
+    pub fn set_inproc_spin(&self, iterations: u32) {
+        self.inner.spin.set(iterations);
+    }
+
+    /// Get how many times the async operations re-check ZMQ_EVENTS before parking the task.
+    pub fn inproc_spin(&self) -> u32 {
+        self.inner.spin.get()
+    }
+
+    /// Enable ZMTP heartbeats.
+    ///
+    /// A PING is sent every `ivl`, and the connection is closed if nothing arrives within
+    /// `timeout` after that. `ttl` tells the peer to close the connection if it hears
+    /// nothing from this socket for that long; libzmq rounds it down to 100 milliseconds.
+    /// Returns an error of kind `InvalidInput` if a duration doesn't fit in `i32` milliseconds.
+    pub fn set_heartbeat(&self, ivl: Duration, timeout: Duration, ttl: Duration) -> io::Result<()> {
+        let (ivl, timeout, ttl) = (millis(ivl)?, millis(timeout)?, millis(ttl)?);
+        self.inner
+            .sock
+            .set_heartbeat_ivl(ivl)
+            .map_err(|e| self.error(e))?;
+        self.inner
+            .sock
+            .set_heartbeat_timeout(timeout)
+            .map_err(|e| self.error(e))?;
+        self.inner
+            .sock
+            .set_heartbeat_ttl(ttl)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Set the integer socket option `opt` (one of the `ZMQ_*` constants of libzmq).
+    ///
+    /// This and the other `*_sockopt_*` methods are for the options without
+    /// a method of their own. Values of other types (e.g. `int64_t`) can be set
+    /// as bytes in native byte order.
+    pub fn set_sockopt_int(&self, opt: i32, val: i32) -> io::Result<()> {
+        self.set_sockopt_bytes(opt, &val.to_ne_bytes())
+    }
+
+    /// Set the binary or string socket option `opt`.
+    pub fn set_sockopt_bytes(&self, opt: i32, val: &[u8]) -> io::Result<()> {
+        // SAFETY: The socket is alive, and libzmq only reads `val.len()` bytes.
+        let rc = unsafe {
+            zmq_sys::zmq_setsockopt(
+                self.inner.raw,
+                opt,
+                val.as_ptr() as *const c_void,
+                val.len(),
+            )
+        };
+        check(rc).map_err(|e| self.error(e))
+    }
+
+    /// Get the integer socket option `opt`.
+    pub fn get_sockopt_int(&self, opt: i32) -> io::Result<i32> {
+        let val = self.get_sockopt_bytes(opt, mem::size_of::<i32>())?;
+        let val = <[u8; 4]>::try_from(&val[..]).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "the option is not an integer")
+        })?;
+        Ok(i32::from_ne_bytes(val))
+    }
+
+    /// Get the binary or string socket option `opt`, of at most `max_len` bytes.
+    ///
+    /// libzmq fails with `EINVAL` if the value doesn't fit in `max_len`.
+    pub fn get_sockopt_bytes(&self, opt: i32, max_len: usize) -> io::Result<Vec<u8>> {
+        let mut val = vec![0u8; max_len];
+        let mut len = max_len;
+        // SAFETY: The socket is alive, and libzmq writes at most `len` bytes.
+        let rc = unsafe {
+            zmq_sys::zmq_getsockopt(
+                self.inner.raw,
+                opt,
+                val.as_mut_ptr() as *mut c_void,
+                &mut len,
+            )
+        };
+        check(rc).map_err(|e| self.error(e))?;
+        val.truncate(len);
+        Ok(val)
+    }
+
+    /// Fail if the socket has been bound or connected, for the options
+    /// which only apply to the connections made after they're set.
+    fn check_unconnected(&self, option: &str) -> io::Result<()> {
+        if self.last_endpoint()?.is_empty() {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} must be set before the socket is bound or connected",
+                    option
+                ),
+            ))
+        }
+    }
+}
+
+/// Turn the return code of a libzmq call into the error in `errno`.
+fn check(rc: i32) -> zmq::Result<()> {
+    if rc == -1 {
+        // SAFETY: Only reads the thread-local errno of libzmq.
+        let errno = unsafe { zmq_sys::zmq_errno() };
+        Err(zmq::Error::from_raw(errno))
+    } else {
+        Ok(())
+    }
+}
+
+fn millis(dur: Duration) -> io::Result<i32> {
+    i32::try_from(dur.as_millis()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "duration is too long for a socket option",
+        )
+    })
+}
@@ -0,0 +1,30 @@
+use crate::Socket;
+use std::{
+    io,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static PAIR_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Create two PAIR sockets connected to each other over inproc, in a context of their own.
+///
+/// Each call uses a unique endpoint, so it's handy for tests. LINGER is set to 0,
+/// so that dropping the sockets (and with them the context) never blocks on
+/// undelivered messages. As with [`Socket::from_socket`], this must be called
+/// within the context of a Tokio runtime.
+pub fn pair() -> io::Result<(Socket, Socket)> {
+    let ctx = zmq::Context::new();
+    let endpoint = format!(
+        "inproc://zmq-async-pair-{}",
+        PAIR_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let a = ctx.socket(zmq::SocketType::PAIR)?;
+    a.set_linger(0)?;
+    a.bind(&endpoint)?;
+    let b = ctx.socket(zmq::SocketType::PAIR)?;
+    b.set_linger(0)?;
+    b.connect(&endpoint)?;
+
+    Ok((Socket::from_socket(a)?, Socket::from_socket(b)?))
+}
@@ -0,0 +1,76 @@
+use crate::Socket;
+use futures::future::poll_fn;
+use log::*;
+use std::{io, task::Poll};
+
+/// Forward messages between `frontend` and `backend` until an error occurs.
+///
+/// This is the async counterpart of `zmq_proxy`. Full multi-part messages are
+/// forwarded, and the socket checked first alternates on every message
+/// so that a busy direction can't starve the other.
+pub async fn proxy(frontend: &Socket, backend: &Socket) -> io::Result<()> {
+    run(frontend, backend, None).await
+}
+
+/// Forward messages as [`proxy`] does, taking commands from `control`.
+///
+/// This is the async counterpart of `zmq_proxy_steerable`. The commands are
+/// `PAUSE` to stop forwarding, `RESUME` to restart it, and `TERMINATE`
+/// to return `Ok(())`. Other commands are ignored.
+pub async fn proxy_steerable(
+    frontend: &Socket,
+    backend: &Socket,
+    control: &Socket,
+) -> io::Result<()> {
+    run(frontend, backend, Some(control)).await
+}
+
+enum Event<'a> {
+    Command(Vec<zmq::Message>),
+    Forward(Vec<zmq::Message>, &'a Socket),
+}
+
+async fn run(frontend: &Socket, backend: &Socket, control: Option<&Socket>) -> io::Result<()> {
+    let mut front_first = true;
+    let mut paused = false;
+
+    loop {
+        let event = poll_fn(|cx| {
+            if let Some(control) = control {
+                if let Poll::Ready(res) = control.poll_read(cx, crate::recv_msgs) {
+                    return Poll::Ready(res.map(Event::Command));
+                }
+            }
+            if paused {
+                return Poll::Pending;
+            }
+
+            let order = if front_first {
+                [(frontend, backend), (backend, frontend)]
+            } else {
+                [(backend, frontend), (frontend, backend)]
+            };
+
+            for (from, to) in order.iter() {
+                if let Poll::Ready(res) = from.poll_read(cx, crate::recv_msgs) {
+                    return Poll::Ready(res.map(|msgs| Event::Forward(msgs, to)));
+                }
+            }
+            Poll::Pending
+        })
+        .await?;
+
+        match event {
+            Event::Command(msgs) => match &msgs[0][..] {
+                b"PAUSE" => paused = true,
+                b"RESUME" => paused = false,
+                b"TERMINATE" => return Ok(()),
+                cmd => warn!("Ignore proxy command: {}", String::from_utf8_lossy(cmd)),
+            },
+            Event::Forward(msgs, to) => {
+                front_first = !front_first;
+                to.send_all(msgs.iter().map(|m| &m[..])).await?;
+            }
+        }
+    }
+}
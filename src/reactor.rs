@@ -0,0 +1,270 @@
+//! The async runtime the ZMQ fd is driven by.
+//!
+//! Tokio is used by default. With the `async-io` feature, the fd is registered to
+//! the reactor of `async-io` instead, which runs on its own thread and works with
+//! any executor (e.g. async-std or smol).
+
+use crate::waker::TaskWaker;
+use ::tokio::time::Instant;
+use std::{future::Future, io, time::Duration};
+
+/// Trace a registration change with the fd and the given fields.
+///
+/// With the `tracing` feature the fields are recorded as structured fields of the event.
+macro_rules! trace_fd {
+    ($msg:literal, $fd:expr $(, $name:ident = $val:expr)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::trace!(fd = $fd $(, $name = ?$val)*, $msg);
+        #[cfg(not(feature = "tracing"))]
+        log::trace!(
+            concat!($msg, ": {}" $(, " ", stringify!($name), "={:?}")*),
+            $fd $(, $val)*
+        );
+    }};
+}
+
+#[cfg(unix)]
+pub use std::os::unix::io::RawFd;
+#[cfg(windows)]
+pub use std::os::windows::io::RawSocket as RawFd;
+
+#[cfg(not(feature = "async-io"))]
+pub use self::tokio::*;
+
+#[cfg(feature = "async-io")]
+pub use self::async_io::*;
+
+#[cfg(not(feature = "async-io"))]
+mod tokio {
+    use super::*;
+    #[cfg(unix)]
+    use ::tokio::io::unix::AsyncFd;
+    #[cfg(unix)]
+    use std::task::{Context, Poll};
+
+    /// The registration of the ZMQ fd.
+    #[cfg(unix)]
+    pub struct Reactor(AsyncFd<RawFd>);
+
+    #[cfg(unix)]
+    impl Reactor {
+        /// Register the fd to the reactor of the current Tokio runtime.
+        pub fn new(fd: RawFd, _read: &TaskWaker, _write: &TaskWaker) -> io::Result<Self> {
+            trace_fd!("Register ZMQ fd", fd, read = true, write = true);
+            Ok(Self(AsyncFd::new(fd)?))
+        }
+
+        /// Wake up the tasks of `read` on the next read event on the fd.
+        pub fn sleep_read(&self, read: &TaskWaker) -> io::Result<()> {
+            trace_fd!(
+                "Wait on ZMQ fd",
+                *self.0.get_ref(),
+                read = true,
+                write = false
+            );
+            // Clear the readiness seen so far, until the waker is registered.
+            let mut cx = Context::from_waker(read.waker());
+            while let Poll::Ready(mut guard) = self.0.poll_read_ready(&mut cx)? {
+                guard.clear_ready();
+            }
+            Ok(())
+        }
+
+        /// Wake up the tasks of `write` on the next write event on the fd.
+        pub fn sleep_write(&self, write: &TaskWaker) -> io::Result<()> {
+            trace_fd!(
+                "Wait on ZMQ fd",
+                *self.0.get_ref(),
+                read = false,
+                write = true
+            );
+            let mut cx = Context::from_waker(write.waker());
+            while let Poll::Ready(mut guard) = self.0.poll_write_ready(&mut cx)? {
+                guard.clear_ready();
+            }
+            Ok(())
+        }
+
+        /// Remove the fd from the reactor.
+        pub fn deregister(self) -> io::Result<()> {
+            trace_fd!("De-register ZMQ fd", self.0.into_inner());
+            Ok(())
+        }
+    }
+
+    /// On Windows the ZMQ notification handle is a `SOCKET`, but Tokio only drives
+    /// sockets it owns through IOCP and can't watch the readiness of a foreign one
+    /// without reading from it (which would eat libzmq's signals). Registration
+    /// fails; the `async-io` feature provides a reactor which can poll it.
+    #[cfg(windows)]
+    pub struct Reactor;
+
+    #[cfg(windows)]
+    impl Reactor {
+        pub fn new(fd: RawFd, _read: &TaskWaker, _write: &TaskWaker) -> io::Result<Self> {
+            trace_fd!("Register ZMQ socket", fd, read = true, write = true);
+            Err(io::Error::other(
+                "polling ZMQ sockets is not supported on Windows",
+            ))
+        }
+
+        pub fn sleep_read(&self, _read: &TaskWaker) -> io::Result<()> {
+            Ok(())
+        }
+
+        pub fn sleep_write(&self, _write: &TaskWaker) -> io::Result<()> {
+            Ok(())
+        }
+
+        pub fn deregister(self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Run `f` for up to `dur`.
+    pub async fn timeout<F: Future>(dur: Duration, f: F) -> Option<F::Output> {
+        ::tokio::time::timeout(dur, f).await.ok()
+    }
+
+    /// Run `f` until `deadline`.
+    pub async fn timeout_at<F: Future>(deadline: Instant, f: F) -> Option<F::Output> {
+        ::tokio::time::timeout_at(deadline, f).await.ok()
+    }
+
+    /// Start the blocking function `f` right away on a thread where blocking is fine.
+    pub fn spawn_blocking<F, R>(f: F) -> impl Future<Output = io::Result<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let handle = ::tokio::task::spawn_blocking(f);
+        async { handle.await.map_err(io::Error::other) }
+    }
+}
+
+#[cfg(feature = "async-io")]
+mod async_io {
+    use super::*;
+    use ::async_io::{Async, Timer};
+    use futures::{
+        channel::oneshot,
+        future::{self, Either},
+        pin_mut,
+    };
+    use std::task::{Context, Poll, Waker};
+
+    #[cfg(unix)]
+    use std::os::unix::io::{AsFd as AsRaw, BorrowedFd as Borrowed};
+    #[cfg(windows)]
+    use std::os::windows::io::{AsSocket as AsRaw, BorrowedSocket as Borrowed};
+
+    /// The fd of a ZMQ socket, which is owned by the socket.
+    #[derive(Debug)]
+    struct Fd(RawFd);
+
+    impl AsRaw for Fd {
+        #[cfg(unix)]
+        fn as_fd(&self) -> Borrowed<'_> {
+            // SAFETY: The socket is closed only after the fd is deregistered.
+            unsafe { Borrowed::borrow_raw(self.0) }
+        }
+
+        #[cfg(windows)]
+        fn as_socket(&self) -> Borrowed<'_> {
+            // SAFETY: The socket is closed only after the fd is deregistered.
+            unsafe { Borrowed::borrow_raw(self.0) }
+        }
+    }
+
+    /// The registration of the ZMQ fd.
+    ///
+    /// The fd only ever becomes readable, whichever direction the socket state changes
+    /// in, and the reactor keeps a single waker for it. So both directions wait for
+    /// the fd to become readable with the waker which wakes up the tasks of both.
+    pub struct Reactor {
+        io: Async<Fd>,
+        waker: Waker,
+    }
+
+    impl Reactor {
+        /// Register the fd to the reactor of `async-io`.
+        pub fn new(fd: RawFd, read: &TaskWaker, write: &TaskWaker) -> io::Result<Self> {
+            trace_fd!("Register ZMQ fd", fd, read = true, write = true);
+            Ok(Self {
+                // libzmq has already made the fd non-blocking.
+                io: Async::new_nonblocking(Fd(fd))?,
+                waker: read.join(write),
+            })
+        }
+
+        /// Wake up the tasks of `read` on the next event on the fd.
+        pub fn sleep_read(&self, _read: &TaskWaker) -> io::Result<()> {
+            trace_fd!(
+                "Wait on ZMQ fd",
+                self.io.get_ref().0,
+                read = true,
+                write = false
+            );
+            self.sleep()
+        }
+
+        /// Wake up the tasks of `write` on the next event on the fd.
+        pub fn sleep_write(&self, _write: &TaskWaker) -> io::Result<()> {
+            trace_fd!(
+                "Wait on ZMQ fd",
+                self.io.get_ref().0,
+                read = false,
+                write = true
+            );
+            self.sleep()
+        }
+
+        /// Remove the fd from the reactor.
+        pub fn deregister(self) -> io::Result<()> {
+            trace_fd!("De-register ZMQ fd", self.io.into_inner()?.0);
+            Ok(())
+        }
+
+        fn sleep(&self) -> io::Result<()> {
+            // An event which has arrived since the last wait has already woken up
+            // the tasks, so just wait for the next one.
+            let mut cx = Context::from_waker(&self.waker);
+            while let Poll::Ready(res) = self.io.poll_readable(&mut cx) {
+                res?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Run `f` for up to `dur`.
+    pub async fn timeout<F: Future>(dur: Duration, f: F) -> Option<F::Output> {
+        pin_mut!(f);
+        match future::select(f, Timer::after(dur)).await {
+            Either::Left((res, _)) => Some(res),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Run `f` until `deadline`.
+    pub async fn timeout_at<F: Future>(deadline: Instant, f: F) -> Option<F::Output> {
+        pin_mut!(f);
+        match future::select(f, Timer::at(deadline.into_std())).await {
+            Either::Left((res, _)) => Some(res),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Start the blocking function `f` right away on a thread of its own.
+    pub fn spawn_blocking<F, R>(f: F) -> impl Future<Output = io::Result<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        std::thread::spawn(move || tx.send(f()));
+        async {
+            rx.await
+                .map_err(|_| io::Error::other("the blocking task has panicked"))
+        }
+    }
+}
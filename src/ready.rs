@@ -0,0 +1,68 @@
+use crate::Socket;
+use futures::future::poll_fn;
+use std::{io, ops::BitOr, task::Poll};
+
+/// The directions to wait for in [`Socket::ready`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interest(zmq::PollEvents);
+
+impl Interest {
+    /// Wait for the socket to become readable.
+    pub const READABLE: Interest = Interest(zmq::POLLIN);
+    /// Wait for the socket to become writable.
+    pub const WRITABLE: Interest = Interest(zmq::POLLOUT);
+}
+
+impl BitOr for Interest {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Interest(self.0 | rhs.0)
+    }
+}
+
+/// The directions a socket is ready for, returned by [`Socket::ready`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Readiness(zmq::PollEvents);
+
+impl Readiness {
+    /// Whether a message can be received.
+    pub fn is_readable(&self) -> bool {
+        self.0.contains(zmq::POLLIN)
+    }
+
+    /// Whether a message can be sent.
+    pub fn is_writable(&self) -> bool {
+        self.0.contains(zmq::POLLOUT)
+    }
+}
+
+impl Socket {
+    /// Wait until the socket is ready for any of the directions of `interest`.
+    ///
+    /// Only the directions of `interest` are reported. As with [`readable`](Self::readable),
+    /// the readiness is only a hint and a following `try_*` call may still fail with
+    /// `WouldBlock`.
+    pub async fn ready(&self, interest: Interest) -> io::Result<Readiness> {
+        poll_fn(|cx| {
+            let events = self.inner.sock.get_events().map_err(|e| self.error(e))?;
+            let ready = events & interest.0;
+            if !ready.is_empty() {
+                return Poll::Ready(Ok(Readiness(ready)));
+            }
+            self.inner.events.set(Some(events));
+
+            if interest.0.contains(zmq::POLLIN) {
+                self.sleep_read(cx)?;
+            }
+            if interest.0.contains(zmq::POLLOUT) {
+                self.sleep_write(cx)?;
+            }
+            // The fd is edge-triggered, so the tasks waiting for the other direction
+            // must see the readiness this has consumed.
+            self.cross_wake(events);
+            Poll::Pending
+        })
+        .await
+    }
+}
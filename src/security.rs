@@ -0,0 +1,71 @@
+use crate::Socket;
+use std::io;
+
+/// Generate a CURVE key pair, returned as Z85-encoded `(public, secret)` keys.
+pub fn curve_keypair() -> io::Result<(String, String)> {
+    let pair = zmq::CurveKeyPair::new()?;
+    Ok((z85(&pair.public_key)?, z85(&pair.secret_key)?))
+}
+
+fn z85(key: &[u8]) -> io::Result<String> {
+    zmq::z85_encode(key).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e)))
+}
+
+impl Socket {
+    /// Make this socket a CURVE server with its secret key.
+    ///
+    /// Keys are either 32 raw bytes or 40 Z85 characters.
+    pub fn set_curve_server(&self, secret: &[u8]) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_curve_server(true)
+            .map_err(|e| self.error(e))?;
+        self.inner
+            .sock
+            .set_curve_secretkey(secret)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Make this socket a CURVE client of the server with `server_public` key.
+    ///
+    /// Keys are in either form as with [`set_curve_server`](Self::set_curve_server).
+    pub fn set_curve_client(
+        &self,
+        server_public: &[u8],
+        client_public: &[u8],
+        client_secret: &[u8],
+    ) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_curve_serverkey(server_public)
+            .map_err(|e| self.error(e))?;
+        self.inner
+            .sock
+            .set_curve_publickey(client_public)
+            .map_err(|e| self.error(e))?;
+        self.inner
+            .sock
+            .set_curve_secretkey(client_secret)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Enable or disable the PLAIN server role on this socket.
+    pub fn set_plain_server(&self, enable: bool) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_plain_server(enable)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Make this socket a PLAIN client with the credentials.
+    pub fn set_plain_client(&self, username: &str, password: &str) -> io::Result<()> {
+        self.inner
+            .sock
+            .set_plain_username(Some(username))
+            .map_err(|e| self.error(e))?;
+        self.inner
+            .sock
+            .set_plain_password(Some(password))
+            .map_err(|e| self.error(e))
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{waker::TaskWaker, Socket};
+use futures::future::poll_fn;
+use std::{
+    borrow::Borrow,
+    cell::Cell,
+    io,
+    task::{Poll, Waker},
+};
+
+/// Receive a multi-part message from whichever of `sockets` has one first.
+///
+/// Returns the index of the socket together with the message. Sockets are checked
+/// in order, so a lower index wins when several have a message at the same time.
+pub async fn recv_any(sockets: &[&Socket]) -> io::Result<(usize, Vec<Vec<u8>>)> {
+    recv_from(sockets, 0).await
+}
+
+/// A set of sockets to receive from and send to in turn.
+///
+/// Unlike [`recv_any`], each receive starts checking from the socket after the one
+/// which got the last message, so a busy socket doesn't starve the others.
+/// Sends rotate the same way, on their own, to spread the messages over the sockets.
+pub struct SocketGroup {
+    sockets: Vec<Socket>,
+    next: Cell<usize>,
+    next_send: Cell<usize>,
+}
+
+impl SocketGroup {
+    /// Create a group of `sockets`, which are shared with their other clones.
+    pub fn new(sockets: Vec<Socket>) -> Self {
+        Self {
+            sockets,
+            next: Cell::new(0),
+            next_send: Cell::new(0),
+        }
+    }
+
+    /// The sockets of the group, in the order given to [`new`](Self::new).
+    pub fn sockets(&self) -> &[Socket] {
+        &self.sockets
+    }
+
+    /// Receive a multi-part message from the next socket which has one.
+    ///
+    /// Returns the index of the socket together with the message.
+    pub async fn recv(&self) -> io::Result<(usize, Vec<Vec<u8>>)> {
+        let (i, msgs) = recv_from(&self.sockets, self.next.get()).await?;
+        self.next.set((i + 1) % self.sockets.len());
+        Ok((i, msgs))
+    }
+
+    /// Send a multi-part message to the next socket which can accept it.
+    ///
+    /// Returns the index of the socket the message has been sent to. If all the sockets
+    /// are blocked (e.g. at SNDHWM), this waits until one of them can take the message.
+    pub async fn send_any<T>(&self, msgs: &[T]) -> io::Result<usize>
+    where
+        T: AsRef<[u8]>,
+    {
+        let i = send_to(&self.sockets, self.next_send.get(), msgs).await?;
+        self.next_send.set((i + 1) % self.sockets.len());
+        Ok(i)
+    }
+}
+
+/// Receive from whichever of `sockets` has a message first, checking from `start` on.
+async fn recv_from<S>(sockets: &[S], start: usize) -> io::Result<(usize, Vec<Vec<u8>>)>
+where
+    S: Borrow<Socket>,
+{
+    if sockets.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no sockets to receive from",
+        ));
+    }
+
+    let mut guard = Unregister {
+        sockets,
+        wakers: |sock| &sock.inner.read,
+        waker: None,
+    };
+
+    poll_fn(|cx| {
+        guard.waker = Some(cx.waker().clone());
+
+        for i in (start..sockets.len()).chain(0..start) {
+            if let Poll::Ready(res) = sockets[i].borrow().poll_recv_multipart(cx) {
+                return Poll::Ready(res.map(|msgs| (i, msgs)));
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Send `msgs` to whichever of `sockets` can take it first, checking from `start` on.
+async fn send_to<S, T>(sockets: &[S], start: usize, msgs: &[T]) -> io::Result<usize>
+where
+    S: Borrow<Socket>,
+    T: AsRef<[u8]>,
+{
+    if sockets.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no sockets to send to",
+        ));
+    }
+
+    let mut guard = Unregister {
+        sockets,
+        wakers: |sock| &sock.inner.write,
+        waker: None,
+    };
+
+    poll_fn(|cx| {
+        guard.waker = Some(cx.waker().clone());
+
+        for i in (start..sockets.len()).chain(0..start) {
+            if let Poll::Ready(res) = sockets[i].borrow().poll_send_multipart(cx, msgs) {
+                return Poll::Ready(res.map(|_| i));
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Removes the task from the sockets it has been waiting for,
+/// whether the receive or send completes or is dropped.
+struct Unregister<'a, S: Borrow<Socket>> {
+    sockets: &'a [S],
+    wakers: fn(&Socket) -> &TaskWaker,
+    waker: Option<Waker>,
+}
+
+impl<S: Borrow<Socket>> Drop for Unregister<'_, S> {
+    fn drop(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            for sock in self.sockets {
+                (self.wakers)(sock.borrow()).unregister(&waker);
+            }
+            // Another future in the same task may be waiting for one of the sockets
+            // under the same waker, so let it poll and register itself again.
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::Socket;
+use futures::{ready, Sink};
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A sink of multi-part messages, created by [`Socket::outgoing`].
+///
+/// The sink buffers a single message; [`start_send`](Sink::start_send) only
+/// queues it, and the message is actually handed to ZMQ when the sink is flushed.
+/// If the socket is not writable, the message stays queued until a later flush
+/// succeeds.
+pub struct Outgoing<'a> {
+    sock: &'a Socket,
+    pending: Option<Vec<Vec<u8>>>,
+}
+
+impl<'a> Outgoing<'a> {
+    pub(crate) fn new(sock: &'a Socket) -> Self {
+        Self {
+            sock,
+            pending: None,
+        }
+    }
+}
+
+impl Sink<Vec<Vec<u8>>> for Outgoing<'_> {
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, msgs: Vec<Vec<u8>>) -> io::Result<()> {
+        self.get_mut().pending = Some(msgs);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if let Some(msgs) = &this.pending {
+            ready!(this.sock.poll_send_multipart(cx, msgs))?;
+            this.pending = None;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
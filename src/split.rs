@@ -0,0 +1,30 @@
+use crate::Socket;
+use std::{io, rc::Rc};
+
+/// The sending half of a [`Socket`], created by [`Socket::split`].
+pub struct SendHalf(Rc<Socket>);
+
+/// The receiving half of a [`Socket`], created by [`Socket::split`].
+pub struct RecvHalf(Rc<Socket>);
+
+pub(crate) fn split(sock: Socket) -> (SendHalf, RecvHalf) {
+    let sock = Rc::new(sock);
+    (SendHalf(sock.clone()), RecvHalf(sock))
+}
+
+impl SendHalf {
+    /// Send a multi-part message.
+    pub async fn send_multipart<T>(&self, msgs: &[T]) -> io::Result<()>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.0.send_multipart(msgs).await
+    }
+}
+
+impl RecvHalf {
+    /// Receive a multi-part message.
+    pub async fn recv_multipart(&self) -> io::Result<Vec<Vec<u8>>> {
+        self.0.recv_multipart().await
+    }
+}
@@ -0,0 +1,155 @@
+use crate::Socket;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Message and byte counts of a socket, returned by [`Socket::stats`].
+///
+/// Each multi-part message counts as one message, and the bytes are the sum
+/// of the sizes of its parts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SocketStats {
+    /// The number of messages sent.
+    pub sent_messages: u64,
+    /// The number of messages received.
+    pub recv_messages: u64,
+    /// The number of bytes sent.
+    pub sent_bytes: u64,
+    /// The number of bytes received.
+    pub recv_bytes: u64,
+}
+
+#[derive(Default)]
+pub struct Counters {
+    sent_messages: AtomicU64,
+    recv_messages: AtomicU64,
+    sent_bytes: AtomicU64,
+    recv_bytes: AtomicU64,
+}
+
+impl Counters {
+    pub fn sent(&self, t: &impl Transfer) {
+        if let Some(bytes) = t.bytes() {
+            self.sent_messages
+                .fetch_add(t.messages() as u64, Ordering::Relaxed);
+            self.sent_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub fn received(&self, t: &impl Transfer) {
+        if let Some(bytes) = t.bytes() {
+            self.recv_messages
+                .fetch_add(t.messages() as u64, Ordering::Relaxed);
+            self.recv_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn get(&self) -> SocketStats {
+        SocketStats {
+            sent_messages: self.sent_messages.load(Ordering::Relaxed),
+            recv_messages: self.recv_messages.load(Ordering::Relaxed),
+            sent_bytes: self.sent_bytes.load(Ordering::Relaxed),
+            recv_bytes: self.recv_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The result of a completed socket operation, as counted in the stats.
+pub trait Transfer {
+    /// The size of the message transferred, or `None` if there was no message.
+    fn bytes(&self) -> Option<usize>;
+
+    /// The number of messages transferred, if there was any.
+    fn messages(&self) -> usize {
+        1
+    }
+}
+
+/// Only waited for readiness.
+impl Transfer for () {
+    fn bytes(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Transferred a message of this size.
+impl Transfer for usize {
+    fn bytes(&self) -> Option<usize> {
+        Some(*self)
+    }
+}
+
+impl Transfer for zmq::Message {
+    fn bytes(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl Transfer for Vec<Vec<u8>> {
+    fn bytes(&self) -> Option<usize> {
+        Some(len(self))
+    }
+}
+
+impl Transfer for Vec<zmq::Message> {
+    fn bytes(&self) -> Option<usize> {
+        Some(self.iter().map(|m| m.len()).sum())
+    }
+}
+
+/// A message received with whether it ended cleanly.
+impl Transfer for (Vec<Vec<u8>>, bool) {
+    fn bytes(&self) -> Option<usize> {
+        Some(len(&self.0))
+    }
+}
+
+impl Transfer for Vec<crate::Frame> {
+    fn bytes(&self) -> Option<usize> {
+        Some(self.iter().map(|f| f.data.len()).sum())
+    }
+}
+
+/// Transferred a batch of multi-part messages.
+impl Transfer for Vec<Vec<Vec<u8>>> {
+    fn bytes(&self) -> Option<usize> {
+        Some(self.iter().map(|m| len(m)).sum())
+    }
+
+    fn messages(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The number and total size of the messages sent in a batch.
+pub struct Batch {
+    pub messages: usize,
+    pub bytes: usize,
+}
+
+impl Transfer for Batch {
+    fn bytes(&self) -> Option<usize> {
+        Some(self.bytes)
+    }
+
+    fn messages(&self) -> usize {
+        self.messages
+    }
+}
+
+/// A message which has been dropped is not counted.
+impl<T: Transfer> Transfer for Option<T> {
+    fn bytes(&self) -> Option<usize> {
+        self.as_ref().and_then(Transfer::bytes)
+    }
+}
+
+/// The total size of the parts of a message.
+pub fn len<T: AsRef<[u8]>>(msgs: &[T]) -> usize {
+    msgs.iter().map(|m| m.as_ref().len()).sum()
+}
+
+impl Socket {
+    /// Get the number of messages and bytes sent and received through this socket.
+    pub fn stats(&self) -> SocketStats {
+        self.inner.stats.get()
+    }
+}
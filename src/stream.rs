@@ -0,0 +1,250 @@
+use crate::Socket;
+use futures::ready;
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    convert::TryFrom,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// What happened on a [`StreamSocket`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamEvent {
+    /// A peer has connected.
+    Connected(Vec<u8>),
+    /// A peer has disconnected.
+    Disconnected(Vec<u8>),
+    /// Data has arrived from a peer.
+    Data(Vec<u8>, Vec<u8>),
+}
+
+/// A STREAM socket, which speaks raw TCP to peers that aren't ZMQ.
+///
+/// Every message is a routing id frame followed by a data frame. An empty data frame
+/// is sent when a peer connects or disconnects (with ZMQ_STREAM_NOTIFY, the default),
+/// and sending one closes the connection to the peer.
+pub struct StreamSocket {
+    sock: Socket,
+    peers: RefCell<HashSet<Vec<u8>>>,
+}
+
+impl StreamSocket {
+    /// Wrap a socket of type STREAM.
+    ///
+    /// Returns an error of kind `InvalidInput` if the socket is of another type.
+    pub fn new(sock: Socket) -> io::Result<Self> {
+        check_type(&sock)?;
+        Ok(Self {
+            sock,
+            peers: RefCell::new(HashSet::new()),
+        })
+    }
+
+    /// Receive the routing id of a peer and the data from it.
+    ///
+    /// The data is empty when the peer has connected or disconnected.
+    pub async fn recv_data(&self) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let (id, data) = parse(self.sock.recv_multipart().await?)?;
+        if data.is_empty() {
+            let mut peers = self.peers.borrow_mut();
+            if !peers.remove(&id) {
+                peers.insert(id.clone());
+            }
+        }
+        Ok((id, data))
+    }
+
+    /// Receive the next event, telling connections and disconnections apart.
+    ///
+    /// The peers known to be connected are tracked from the empty data frames,
+    /// so every message must be received through this or [`recv_data`](Self::recv_data).
+    pub async fn recv_event(&self) -> io::Result<StreamEvent> {
+        let (id, data) = self.recv_data().await?;
+        if !data.is_empty() {
+            Ok(StreamEvent::Data(id, data))
+        } else if self.peers.borrow().contains(&id) {
+            Ok(StreamEvent::Connected(id))
+        } else {
+            Ok(StreamEvent::Disconnected(id))
+        }
+    }
+
+    /// Send `data` to the peer of the routing id `id`.
+    pub async fn send_data(&self, id: &[u8], data: &[u8]) -> io::Result<()> {
+        self.sock.send_multipart(&[id, data]).await
+    }
+
+    /// Close the connection to the peer of the routing id `id`.
+    pub async fn disconnect(&self, id: &[u8]) -> io::Result<()> {
+        self.sock.send_multipart(&[id, &[]]).await?;
+        self.peers.borrow_mut().remove(id);
+        Ok(())
+    }
+
+    /// The underlying socket.
+    pub fn get_ref(&self) -> &Socket {
+        &self.sock
+    }
+
+    /// Take the underlying socket out.
+    pub fn into_inner(self) -> Socket {
+        self.sock
+    }
+}
+
+/// A plain byte stream to a single peer of a STREAM socket.
+///
+/// The peer is the first one heard from (e.g. the connection made by connecting the
+/// socket), unless given with [`with_peer`](Self::with_peer); the data from any other
+/// peer is discarded. Writes wait for the peer to be known, and the routing id frame
+/// is added to each of them. Reads return EOF once the peer has disconnected.
+///
+/// Shutting down closes the connection. libzmq does it in the background as the socket
+/// is used, so the socket must keep being used (e.g. by other tasks, or by
+/// [`Socket::events`]) for the peer to see it before the socket is dropped.
+pub struct StreamIo {
+    sock: Socket,
+    peer: Option<Vec<u8>>,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+impl StreamIo {
+    /// Wrap a socket of type STREAM, talking to the first peer heard from.
+    ///
+    /// Returns an error of kind `InvalidInput` if the socket is of another type.
+    pub fn new(sock: Socket) -> io::Result<Self> {
+        check_type(&sock)?;
+        Ok(Self {
+            sock,
+            peer: None,
+            buf: vec![],
+            pos: 0,
+            eof: false,
+        })
+    }
+
+    /// Wrap a socket of type STREAM, talking to the peer of the routing id `id`.
+    ///
+    /// The peer must be connected already (e.g. [`StreamEvent::Connected`] has been
+    /// received), since the next empty data frame from it is taken as the disconnection.
+    pub fn with_peer(sock: Socket, id: Vec<u8>) -> io::Result<Self> {
+        let mut io = Self::new(sock)?;
+        io.peer = Some(id);
+        Ok(io)
+    }
+
+    /// The routing id of the peer, once known.
+    pub fn peer(&self) -> Option<&[u8]> {
+        self.peer.as_deref()
+    }
+
+    /// The underlying socket.
+    pub fn get_ref(&self) -> &Socket {
+        &self.sock
+    }
+
+    /// Take the underlying socket out.
+    pub fn into_inner(self) -> Socket {
+        self.sock
+    }
+
+    /// Receive a message, keeping the data from the peer in `buf`, which must be consumed.
+    fn poll_recv(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        let (id, data) = parse(ready!(self.sock.poll_recv_multipart(cx))?)?;
+        match &self.peer {
+            // Adopted on the connection, or on its first data without ZMQ_STREAM_NOTIFY.
+            None => self.peer = Some(id),
+            Some(peer) if *peer == id => self.eof = data.is_empty(),
+            Some(_) => return Poll::Ready(Ok(())),
+        }
+        if !data.is_empty() {
+            self.buf = data;
+            self.pos = 0;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for StreamIo {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut ReadBuf,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        while this.pos == this.buf.len() && !this.eof {
+            ready!(this.poll_recv(cx))?;
+        }
+        let rest = &this.buf[this.pos..];
+        let n = rest.len().min(buf.remaining());
+        buf.put_slice(&rest[..n]);
+        this.pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for StreamIo {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, data: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // An empty data frame would close the connection.
+        if data.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if this.eof {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "the peer has disconnected",
+            )));
+        }
+        loop {
+            if let Some(peer) = &this.peer {
+                ready!(this.sock.poll_send_multipart(cx, &[&peer[..], data]))?;
+                return Poll::Ready(Ok(data.len()));
+            }
+            ready!(this.poll_recv(cx))?;
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if let (Some(peer), false) = (&this.peer, this.eof) {
+            ready!(this.sock.poll_send_multipart(cx, &[&peer[..], &[]]))?;
+            this.eof = true;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Make sure `sock` is a STREAM socket.
+fn check_type(sock: &Socket) -> io::Result<()> {
+    if sock.socket().get_socket_type()? != zmq::SocketType::STREAM {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a STREAM socket",
+        ));
+    }
+    Ok(())
+}
+
+/// Split a STREAM message into the routing id and the data.
+fn parse(frames: Vec<Vec<u8>>) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    match <[Vec<u8>; 2]>::try_from(frames) {
+        Ok([id, data]) => Ok((id, data)),
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "malformed STREAM message",
+        )),
+    }
+}
@@ -0,0 +1,65 @@
+use crate::Socket;
+use futures::{ready, Stream};
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A stream of messages split into the topic and the body, created by
+/// [`Socket::topic_stream`].
+///
+/// The first part of each message is the topic and the rest is the body,
+/// which is empty for a single-part message.
+pub struct TopicStream<'a> {
+    sock: &'a Socket,
+}
+
+/// A subscription change received on an XPUB socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    /// `true` for a subscription, `false` for an unsubscription.
+    pub subscribe: bool,
+    /// The topic to (un)subscribe.
+    pub topic: Vec<u8>,
+}
+
+impl Socket {
+    /// Receive a subscription change on this XPUB socket.
+    ///
+    /// Returns an error of kind `InvalidData` if the message is not a subscription
+    /// (`0x01` followed by the topic) nor an unsubscription (`0x00` and the topic).
+    pub async fn recv_subscription(&self) -> io::Result<Subscription> {
+        let msg = self.recv().await?;
+
+        match msg.split_first() {
+            Some((&flag, topic)) if flag <= 1 => Ok(Subscription {
+                subscribe: flag == 1,
+                topic: topic.to_vec(),
+            }),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a subscription message",
+            )),
+        }
+    }
+
+    /// Create a stream of the messages received on this (SUB) socket,
+    /// split into the topic and the body.
+    pub fn topic_stream(&self) -> TopicStream<'_> {
+        TopicStream { sock: self }
+    }
+}
+
+impl Stream for TopicStream<'_> {
+    type Item = io::Result<(Vec<u8>, Vec<Vec<u8>>)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let res = ready!(self.sock.poll_recv_multipart(cx));
+
+        Poll::Ready(Some(res.map(|mut msgs| {
+            let body = msgs.split_off(1);
+            (msgs.pop().unwrap(), body)
+        })))
+    }
+}
@@ -0,0 +1,112 @@
+use futures::task::{waker, ArcWake};
+use std::{
+    sync::{Arc, Mutex, Weak},
+    task::Waker,
+};
+
+/// Wakers of the tasks waiting for the same socket event.
+///
+/// Any number of tasks can wait at the same time. They're all woken up together
+/// and re-check the socket state by themselves. This is why a single-slot
+/// `AtomicWaker` doesn't fit here: a second waiter would replace the first one.
+pub struct TaskWaker {
+    wakers: Arc<Wakers>,
+    waker: Waker,
+}
+
+struct Wakers(Mutex<Vec<Waker>>);
+
+/// Wakers of the tasks waiting for either of two socket events.
+#[cfg(feature = "async-io")]
+struct Both(Arc<Wakers>, Arc<Wakers>);
+
+/// A handle to wake up the tasks of a [`TaskWaker`] without keeping it alive.
+pub struct WeakTaskWaker(Weak<Wakers>);
+
+impl ArcWake for Wakers {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        // Take the wakers out first, so that a waker which re-registers
+        // while being woken up doesn't deadlock on the lock.
+        let wakers = std::mem::take(&mut *arc_self.0.lock().unwrap());
+        for w in wakers {
+            w.wake();
+        }
+    }
+}
+
+#[cfg(feature = "async-io")]
+impl ArcWake for Both {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        ArcWake::wake_by_ref(&arc_self.0);
+        ArcWake::wake_by_ref(&arc_self.1);
+    }
+}
+
+impl TaskWaker {
+    pub fn new() -> Self {
+        let wakers = Arc::new(Wakers(Mutex::new(Vec::new())));
+        let waker = waker(wakers.clone());
+        Self { wakers, waker }
+    }
+
+    /// Register a task to be woken up
+    pub fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.0.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    /// Unregister a task
+    pub fn unregister(&self, waker: &Waker) {
+        self.wakers
+            .0
+            .lock()
+            .unwrap()
+            .retain(|w| !w.will_wake(waker));
+    }
+
+    /// Whether any task is registered.
+    pub fn is_waiting(&self) -> bool {
+        !self.wakers.0.lock().unwrap().is_empty()
+    }
+
+    /// Wake up all the registered tasks
+    pub fn wake(&self) {
+        self.waker.wake_by_ref();
+    }
+
+    /// The waker which wakes up all the registered tasks.
+    ///
+    /// This is the waker to hand to the reactor, which itself only keeps
+    /// the last waker it has been given.
+    #[cfg(not(feature = "async-io"))]
+    pub fn waker(&self) -> &Waker {
+        &self.waker
+    }
+
+    /// The waker which wakes up the tasks of both `self` and `other`.
+    #[cfg(feature = "async-io")]
+    pub fn join(&self, other: &TaskWaker) -> Waker {
+        waker(Arc::new(Both(self.wakers.clone(), other.wakers.clone())))
+    }
+
+    /// Make a handle which doesn't keep the wakers alive.
+    pub fn downgrade(&self) -> WeakTaskWaker {
+        WeakTaskWaker(Arc::downgrade(&self.wakers))
+    }
+}
+
+impl WeakTaskWaker {
+    /// Wake up all the registered tasks, if the `TaskWaker` is still alive.
+    pub fn wake(&self) {
+        if let Some(wakers) = self.0.upgrade() {
+            ArcWake::wake_by_ref(&wakers);
+        }
+    }
+
+    /// Whether the `TaskWaker` is still alive.
+    pub fn is_alive(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "async-io")]
+
+use std::time::Duration;
+
+#[test]
+fn without_tokio() {
+    let ctx = zmq::Context::new();
+
+    futures::executor::block_on(async {
+        let rx =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "tcp://127.0.0.1:*")
+                .await
+                .unwrap();
+        let tx = zmq_async::Socket::connect(
+            &ctx,
+            zmq_async::zmq::SocketType::PUSH,
+            &rx.last_endpoint().unwrap(),
+        )
+        .await
+        .unwrap();
+
+        for i in 0..100 {
+            tx.send(format!("{}", i)).await.unwrap();
+            assert_eq!(format!("{}", i), rx.recv_string().await.unwrap());
+        }
+        assert!(rx
+            .recv_timeout(Duration::from_millis(50))
+            .await
+            .unwrap()
+            .is_none());
+    });
+}
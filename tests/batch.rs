@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+#[test]
+fn recv_batch() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (tx, rx) = zmq_async::pair().unwrap();
+
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            rx.recv_batch(0).await.unwrap_err().kind()
+        );
+
+        // Waits for the first message.
+        let (batch, _) = futures::join!(rx.recv_batch(3), async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            tx.send_multipart(&["a", "b"]).await.unwrap();
+        });
+        assert_eq!(vec![vec![b"a".to_vec(), b"b".to_vec()]], batch.unwrap());
+
+        for i in 0..5u8 {
+            tx.send(&[i]).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let batch = rx.recv_batch(3).await.unwrap();
+        assert_eq!(vec![vec![vec![0]], vec![vec![1]], vec![vec![2]]], batch);
+        let batch = rx.recv_batch(3).await.unwrap();
+        assert_eq!(vec![vec![vec![3]], vec![vec![4]]], batch);
+
+        let stats = rx.stats();
+        assert_eq!(6, stats.recv_messages);
+        assert_eq!(7, stats.recv_bytes);
+    });
+}
+
+#[test]
+fn send_batch() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .rcvhwm(1)
+            .bind("inproc://send-batch")
+            .await
+            .unwrap();
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .sndhwm(1)
+            .connect("inproc://send-batch")
+            .await
+            .unwrap();
+
+        let msgs: Vec<_> = (0..10u8).map(|i| vec![vec![i], b"x".to_vec()]).collect();
+        assert_eq!(0, tx.send_batch(&[]).await.unwrap());
+
+        // Only part of the batch fits in the queues.
+        let sent = tx.send_batch(&msgs).await.unwrap();
+        assert!(sent > 0 && sent < msgs.len());
+        assert_eq!(sent as u64, tx.stats().sent_messages);
+
+        // The rest is sent as the receiver makes room.
+        let rest = async {
+            let mut total = sent;
+            while total < msgs.len() {
+                total += tx.send_batch(&msgs[total..]).await.unwrap();
+            }
+        };
+        let recv = async {
+            let mut recvd = vec![];
+            while recvd.len() < msgs.len() {
+                recvd.push(rx.recv_multipart().await.unwrap());
+            }
+            recvd
+        };
+        let (_, recvd) = futures::join!(rest, recv);
+        assert_eq!(msgs, recvd);
+        assert_eq!(10, tx.stats().sent_messages);
+        assert_eq!(20, tx.stats().sent_bytes);
+    });
+}
+
+#[test]
+fn send_batch_failure() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let router = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+            sock.set_router_mandatory(true).unwrap();
+            sock.bind("inproc://send-batch-failure").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let dealer = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::DEALER)
+            .identity(b"peer")
+            .connect("inproc://send-batch-failure")
+            .await
+            .unwrap();
+        dealer.send("hi").await.unwrap();
+        router.recv_multipart().await.unwrap();
+
+        // The second message can't be routed.
+        let msgs = vec![
+            vec![b"peer".to_vec(), b"a".to_vec()],
+            vec![b"nobody".to_vec(), b"b".to_vec()],
+            vec![b"peer".to_vec(), b"c".to_vec()],
+        ];
+        assert_eq!(1, router.send_batch(&msgs).await.unwrap());
+        assert_eq!(1, router.stats().sent_messages);
+        assert_eq!(vec![b"a".to_vec()], dealer.recv_multipart().await.unwrap());
+
+        let err = router.send_batch(&msgs[1..]).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::HostUnreachable, err.kind());
+        assert_eq!(1, router.send_batch(&msgs[2..]).await.unwrap());
+        assert_eq!(vec![b"c".to_vec()], dealer.recv_multipart().await.unwrap());
+    });
+}
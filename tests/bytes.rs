@@ -0,0 +1,44 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn bytes() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+
+        // A small part is held inline in the message, a large one isn't.
+        let large = vec![7u8; 1000];
+        a.send_messages(vec![
+            Bytes::from_static(b"small"),
+            Bytes::from(large.clone()),
+        ])
+        .await
+        .unwrap();
+        let parts = b.recv_multipart_as::<Bytes>().await.unwrap();
+
+        // The parts outlive the socket they were received with.
+        drop(b);
+        let tail = parts[1].slice(990..);
+        drop(parts[1].clone());
+        assert_eq!(&b"small"[..], &parts[0][..]);
+        assert_eq!(&large[..], &parts[1][..]);
+        assert_eq!(&[7u8; 10][..], &tail[..]);
+
+        let (a, b) = zmq_async::pair().unwrap();
+        a.send_messages(vec![BytesMut::from(&b"mut"[..])])
+            .await
+            .unwrap();
+        a.send(Bytes::from_static(b"ref")).await.unwrap();
+        assert_eq!(
+            vec![Bytes::from_static(b"mut")],
+            b.recv_multipart_try_as::<Bytes>().await.unwrap()
+        );
+        assert_eq!(
+            Bytes::from_static(b"ref"),
+            <Bytes as zmq_async::FromMessage>::from(b.recv().await.unwrap())
+        );
+    });
+}
@@ -0,0 +1,45 @@
+use futures::FutureExt;
+use std::time::Duration;
+
+#[test]
+fn recv_in_select() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://cancel").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://cancel").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let send = async {
+            for i in 0..100u8 {
+                tx.send(&[i]).await.unwrap();
+                tokio::time::sleep(Duration::from_micros(100)).await;
+            }
+        };
+        let recv = async {
+            let mut received = vec![];
+            let mut ticks = 0;
+            while received.len() < 100 {
+                futures::select! {
+                    msgs = rx.recv_multipart().fuse() => received.push(msgs.unwrap()),
+                    _ = tokio::time::sleep(Duration::from_micros(50)).fuse() => ticks += 1,
+                }
+            }
+            assert!(ticks > 0);
+            received
+        };
+
+        let (_, received) = futures::join!(send, recv);
+        let expected: Vec<_> = (0..100u8).map(|i| vec![vec![i]]).collect();
+        assert_eq!(expected, received);
+    });
+}
@@ -0,0 +1,39 @@
+#[test]
+fn clone() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://clone")
+            .await
+            .unwrap();
+        let mut tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://clone")
+                .await
+                .unwrap();
+        assert!(tx.try_socket_mut().is_some());
+
+        let tx2 = tx.clone();
+        assert!(tx.try_socket_mut().is_none());
+
+        // A task receives while another one sends through both clones.
+        let recv = async {
+            let a = rx.recv_multipart().await.unwrap();
+            let b = rx.recv_multipart().await.unwrap();
+            (a, b)
+        };
+        let send = async {
+            tx.send("a").await.unwrap();
+            tx2.send("b").await.unwrap();
+        };
+        let ((a, b), ()) = futures::join!(recv, send);
+        assert_eq!(vec![b"a".to_vec()], a);
+        assert_eq!(vec![b"b".to_vec()], b);
+
+        drop(tx2);
+        assert!(tx.try_socket_mut().is_some());
+        tx.socket_mut().set_sndhwm(10).unwrap();
+        assert_eq!(10, tx.socket().get_sndhwm().unwrap());
+    });
+}
@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+#[test]
+fn close() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://close").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://close").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.set_linger(1000).unwrap();
+        assert_eq!(1000, tx.get_linger().unwrap());
+        tx.send("bye").await.unwrap();
+        tx.close().await.unwrap();
+        assert_eq!(vec![b"bye".to_vec()], rx.recv_multipart().await.unwrap());
+
+        // No peer to take messages, so only LINGER decides when to give up.
+        let lonely = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        lonely.set_linger(0).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), lonely.close())
+            .await
+            .unwrap()
+            .unwrap();
+    });
+}
+
+#[test]
+fn flush() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .rcvhwm(1)
+            .bind("inproc://flush")
+            .await
+            .unwrap();
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .sndhwm(1)
+            .connect("inproc://flush")
+            .await
+            .unwrap();
+
+        tx.flush().await.unwrap();
+
+        let mut sent = 0;
+        while tx.try_send_multipart(&["x"]).is_ok() {
+            sent += 1;
+        }
+        assert!(tokio::time::timeout(Duration::from_millis(50), tx.flush())
+            .await
+            .is_err());
+
+        let (flushed, ()) = futures::join!(tx.flush(), async {
+            for _ in 0..sent {
+                rx.recv_multipart().await.unwrap();
+            }
+        });
+        flushed.unwrap();
+    });
+}
+
+#[test]
+fn close_default_linger() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        // Nothing to flush from a socket which can't send.
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://close-default").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        assert_eq!(-1, rx.get_linger().unwrap());
+        tokio::time::timeout(Duration::from_secs(1), rx.close())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Nor from one nothing has been sent through.
+        let lonely = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        assert_eq!(-1, lonely.get_linger().unwrap());
+        tokio::time::timeout(Duration::from_secs(1), lonely.close())
+            .await
+            .unwrap()
+            .unwrap();
+    });
+}
+
+#[test]
+fn close_flushes_with_default_linger() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .bind("inproc://close-flush")
+            .await
+            .unwrap();
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .connect("inproc://close-flush")
+            .await
+            .unwrap();
+
+        assert_eq!(-1, tx.get_linger().unwrap());
+        tx.send("bye").await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), tx.close())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec![b"bye".to_vec()], rx.recv_multipart().await.unwrap());
+    });
+}
+
+#[test]
+fn flush_without_backpressure() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        // A REP which has replied isn't writable until the next request.
+        let rep =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::REP, "inproc://flush-rep")
+                .await
+                .unwrap();
+        let req =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::REQ, "inproc://flush-rep")
+                .await
+                .unwrap();
+        req.send("ping").await.unwrap();
+        rep.recv_multipart().await.unwrap();
+        rep.send("pong").await.unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(1), rep.flush())
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::Unsupported, err.kind());
+
+        // Nor is a DEALER whose peer has gone, though nothing is queued.
+        let dealer = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::DEALER,
+            "tcp://127.0.0.1:*",
+        )
+        .await
+        .unwrap();
+        let addr = dealer.last_endpoint().unwrap();
+        let peer = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::DEALER, &addr)
+            .await
+            .unwrap();
+        dealer.send("a").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], peer.recv_multipart().await.unwrap());
+        peer.set_linger(0).unwrap();
+        drop(peer);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while dealer.is_writable_now().unwrap() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), dealer.flush())
+            .await
+            .unwrap()
+            .unwrap();
+    });
+}
+
+#[test]
+fn close_without_backpressure() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rep =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::REP, "inproc://close-rep")
+                .await
+                .unwrap();
+        let req =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::REQ, "inproc://close-rep")
+                .await
+                .unwrap();
+        req.send("ping").await.unwrap();
+        rep.recv_multipart().await.unwrap();
+        rep.send("pong").await.unwrap();
+        rep.set_linger(2000).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), rep.close())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec![b"pong".to_vec()], req.recv_multipart().await.unwrap());
+
+        let dealer = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::DEALER,
+            "tcp://127.0.0.1:*",
+        )
+        .await
+        .unwrap();
+        let addr = dealer.last_endpoint().unwrap();
+        let peer = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::DEALER, &addr)
+            .await
+            .unwrap();
+        dealer.send("a").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], peer.recv_multipart().await.unwrap());
+        peer.set_linger(0).unwrap();
+        drop(peer);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while dealer.is_writable_now().unwrap() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        dealer.set_linger(2000).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), dealer.close())
+            .await
+            .unwrap()
+            .unwrap();
+    });
+}
@@ -0,0 +1,52 @@
+use futures::{SinkExt, StreamExt};
+use std::io;
+
+/// Sends a key and a value as two parts.
+struct KeyValue;
+
+impl zmq_async::Codec for KeyValue {
+    type Item = (String, u32);
+
+    fn encode(&mut self, (key, value): Self::Item) -> Vec<Vec<u8>> {
+        vec![key.into_bytes(), value.to_be_bytes().to_vec()]
+    }
+
+    fn decode(&mut self, msgs: Vec<Vec<u8>>) -> io::Result<Self::Item> {
+        match &msgs[..] {
+            [key, value] if value.len() == 4 => Ok((
+                String::from_utf8_lossy(key).into_owned(),
+                u32::from_be_bytes([value[0], value[1], value[2], value[3]]),
+            )),
+            _ => Err(io::ErrorKind::InvalidData.into()),
+        }
+    }
+}
+
+#[test]
+fn framed() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let mut rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://codec").unwrap();
+            zmq_async::Framed::new(zmq_async::Socket::new(sock).await.unwrap(), KeyValue)
+        };
+        let mut tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://codec").unwrap();
+            zmq_async::Framed::new(zmq_async::Socket::new(sock).await.unwrap(), KeyValue)
+        };
+
+        tx.send(("a".to_string(), 1)).await.unwrap();
+        tx.send(("b".to_string(), 2)).await.unwrap();
+        assert_eq!(("a".to_string(), 1), rx.next().await.unwrap().unwrap());
+        assert_eq!(("b".to_string(), 2), rx.next().await.unwrap().unwrap());
+
+        tx.get_ref().send("broken").await.unwrap();
+        let err = rx.next().await.unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    });
+}
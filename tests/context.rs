@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+#[test]
+fn terminate() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let ctx = zmq_async::Context::new();
+
+        let pull = ctx.socket(zmq_async::zmq::SocketType::PULL).await.unwrap();
+        pull.set_linger(0).unwrap();
+        pull.socket().bind("inproc://context").unwrap();
+
+        let recv = async move {
+            let err = pull.recv_multipart().await.unwrap_err();
+            drop(pull);
+            err
+        };
+
+        let (res, err) = tokio::time::timeout(Duration::from_secs(5), async {
+            futures::join!(ctx.terminate(), recv)
+        })
+        .await
+        .unwrap();
+        res.unwrap();
+        assert_eq!(std::io::ErrorKind::BrokenPipe, err.kind());
+    });
+}
@@ -0,0 +1,153 @@
+#[test]
+fn recv_multipart_as() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://convert").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://convert").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_multipart(&["hello", "world"]).await.unwrap();
+        assert_eq!(
+            vec!["hello".to_string(), "world".to_string()],
+            rx.recv_multipart_as::<String>().await.unwrap()
+        );
+
+        tx.send_multipart(&[&[0u8, 1][..], &[2u8][..]])
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![vec![0u8, 1], vec![2u8]],
+            rx.recv_multipart_as::<Vec<u8>>().await.unwrap()
+        );
+
+        tx.send_multipart(&[
+            &1u32.to_be_bytes()[..],
+            &[1u8, 2][..],
+            &[0u8, 0, 0, 1, 0, 0, 0, 2][..],
+        ])
+        .await
+        .unwrap();
+        assert_eq!(
+            vec![1u32, 0x0102, 2],
+            rx.recv_multipart_as::<u32>().await.unwrap()
+        );
+
+        tx.send_multipart(&["zero", "copy"]).await.unwrap();
+        let msgs = rx.recv_multipart_msg().await.unwrap();
+        assert_eq!(Some("zero"), msgs[0].as_str());
+        assert_eq!(Some("copy"), msgs[1].as_str());
+    });
+}
+
+#[test]
+fn recv_multipart_try_as() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://try_convert").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://try_convert").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_multipart(&["hello", "world"]).await.unwrap();
+        assert_eq!(
+            vec!["hello".to_string(), "world".to_string()],
+            rx.recv_multipart_try_as::<String>().await.unwrap()
+        );
+
+        tx.send_multipart(&[&b"ok"[..], &[0xffu8, 0xfe][..]])
+            .await
+            .unwrap();
+        let err = rx.recv_multipart_try_as::<String>().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+
+        tx.send_multipart(&[&7u64.to_be_bytes()[..]]).await.unwrap();
+        assert_eq!(vec![7u64], rx.recv_multipart_try_as::<u64>().await.unwrap());
+
+        tx.send_multipart(&[&[1u8, 2][..]]).await.unwrap();
+        let err = rx.recv_multipart_try_as::<u64>().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    });
+}
+
+#[test]
+fn recv_multipart_typed() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://typed").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://typed").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_multipart(&[&[1u8][..], &b"hello"[..]])
+            .await
+            .unwrap();
+        let (id, body) = rx
+            .recv_multipart_typed::<(Vec<u8>, String)>()
+            .await
+            .unwrap();
+        assert_eq!(vec![1u8], id);
+        assert_eq!("hello", body);
+
+        tx.send_multipart(&["a", "b", "c"]).await.unwrap();
+        let err = rx
+            .recv_multipart_typed::<(Vec<u8>, String)>()
+            .await
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    });
+}
+
+#[test]
+fn strings() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://strings").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://strings").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_str("hello").await.unwrap();
+        assert_eq!("hello", rx.recv_string().await.unwrap());
+
+        tx.send(&[0xffu8, 0xfe]).await.unwrap();
+        let err = rx.recv_string().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    });
+}
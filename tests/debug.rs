@@ -0,0 +1,30 @@
+#[test]
+fn debug() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sock = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://debug")
+            .await
+            .unwrap();
+        assert_eq!(
+            "Socket { type: PULL, endpoint: Some(\"inproc://debug\"), read_waiting: false, write_waiting: false }",
+            format!("{:?}", sock)
+        );
+
+        let unbound = zmq_async::Socket::new(ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            "Socket { type: PUSH, endpoint: None, read_waiting: false, write_waiting: false }",
+            format!("{:?}", unbound)
+        );
+
+        // A pending receive is registered for wakeup.
+        let recv = sock.recv_multipart();
+        futures::pin_mut!(recv);
+        assert!(futures::poll!(recv.as_mut()).is_pending());
+        assert!(format!("{:?}", sock).contains("read_waiting: true"));
+    });
+}
@@ -0,0 +1,28 @@
+#[test]
+fn drain() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://drain")
+            .await
+            .unwrap();
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://drain")
+                .await
+                .unwrap();
+
+        assert_eq!(0, rx.drain().unwrap());
+
+        tx.send("a").await.unwrap();
+        tx.send_multipart(&["b", "c", "d"]).await.unwrap();
+        tx.send("e").await.unwrap();
+        rx.readable().await.unwrap();
+        assert_eq!(3, rx.drain().unwrap());
+        assert_eq!(0, rx.drain().unwrap());
+
+        tx.send("f").await.unwrap();
+        assert_eq!(vec![b"f".to_vec()], rx.recv_multipart().await.unwrap());
+    });
+}
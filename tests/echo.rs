@@ -1,12 +1,12 @@
 #[test]
 fn echo() {
-    let mut rt = tokio::runtime::Runtime::new().unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
 
     let ctx = zmq::Context::new();
     let ctx2 = ctx.clone();
 
     std::thread::spawn(move || {
-        let mut rt = tokio::runtime::Runtime::new().unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
 
         rt.block_on(async {
             let srv = {
@@ -17,7 +17,7 @@ fn echo() {
 
             loop {
                 let msgs = srv.recv_multipart().await.unwrap();
-                srv.send_multipart(msgs).await.unwrap();
+                srv.send_multipart(&msgs).await.unwrap();
             }
         });
     });
@@ -36,3 +36,88 @@ fn echo() {
         );
     });
 }
+
+#[test]
+fn echo_spin() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+    let ctx2 = ctx.clone();
+
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        rt.block_on(async {
+            let srv = {
+                let sock = ctx2.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+                sock.bind("inproc://channel-spin").unwrap();
+                zmq_async::Socket::new(sock).await.unwrap()
+            };
+            srv.set_inproc_spin(100);
+
+            loop {
+                let msgs = srv.recv_multipart().await.unwrap();
+                srv.send_multipart(&msgs).await.unwrap();
+            }
+        });
+    });
+
+    rt.block_on(async {
+        let cli = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            sock.connect("inproc://channel-spin").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        assert_eq!(0, cli.inproc_spin());
+        cli.set_inproc_spin(100);
+        assert_eq!(100, cli.inproc_spin());
+
+        // The replies are picked up whether they arrive within the spin or after it.
+        for i in 0..100 {
+            let msg = vec![i.to_string().into_bytes()];
+            cli.send_multipart(&msg).await.unwrap();
+            assert_eq!(msg, cli.recv_multipart().await.unwrap());
+        }
+    });
+}
+
+#[test]
+fn echo_tcp() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+    let ctx2 = ctx.clone();
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        rt.block_on(async {
+            let srv = {
+                let sock = ctx2.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+                sock.bind("tcp://127.0.0.1:*").unwrap();
+                zmq_async::Socket::new(sock).await.unwrap()
+            };
+            tx.send(srv.last_endpoint().unwrap()).unwrap();
+
+            loop {
+                let msgs = srv.recv_multipart().await.unwrap();
+                srv.send_multipart(&msgs).await.unwrap();
+            }
+        });
+    });
+
+    let endpoint = rx.recv().unwrap();
+    assert!(!endpoint.ends_with(":*"));
+
+    rt.block_on(async {
+        let cli = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            sock.connect(&endpoint).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        cli.send_multipart(&["hi"]).await.unwrap();
+        assert_eq!(vec![b"hi".to_vec()], cli.recv_multipart().await.unwrap());
+    });
+}
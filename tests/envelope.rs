@@ -0,0 +1,44 @@
+#[test]
+fn envelope() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let router = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+            sock.bind("inproc://envelope").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let req = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REQ).unwrap();
+            sock.connect("inproc://envelope").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let dealer = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            sock.set_identity(b"dealer").unwrap();
+            sock.connect("inproc://envelope").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        // REQ adds the empty delimiter.
+        req.send_multipart(&["hello"]).await.unwrap();
+        let env = zmq_async::Envelope::recv(&router).await.unwrap();
+        assert_eq!(2, env.routing().len());
+        assert_eq!(&[b"hello".to_vec()], env.payload());
+        env.reply(&router, &["world"]).await.unwrap();
+        assert_eq!(vec![b"world".to_vec()], req.recv_multipart().await.unwrap());
+
+        // DEALER sends raw frames.
+        dealer.send_multipart(&["a", "b"]).await.unwrap();
+        let env = zmq_async::Envelope::recv(&router).await.unwrap();
+        assert_eq!(&[b"dealer".to_vec()], env.routing());
+        assert_eq!(&[b"a".to_vec(), b"b".to_vec()], env.payload());
+        env.reply(&router, env.payload()).await.unwrap();
+        assert_eq!(
+            vec![b"a".to_vec(), b"b".to_vec()],
+            dealer.recv_multipart().await.unwrap()
+        );
+    });
+}
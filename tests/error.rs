@@ -0,0 +1,67 @@
+use zmq_async::Error;
+
+#[test]
+fn zmq_errors() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sock =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PAIR, "inproc://error")
+                .await
+                .unwrap();
+
+        let err = Error::from(sock.connect_to("bogus://error").unwrap_err());
+        assert_eq!(Some(zmq::Error::EPROTONOSUPPORT), err.zmq());
+
+        // Converting back gives the same io::Error.
+        let err = Error::from(sock.disconnect("inproc://nowhere").unwrap_err());
+        assert_eq!(Some(zmq::Error::ENOENT), err.zmq());
+        let err = std::io::Error::from(err);
+        assert_eq!(std::io::ErrorKind::NotFound, err.kind());
+        assert_eq!(Some(zmq::Error::ENOENT), Error::from(err).zmq());
+
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PAIR, "inproc://error")
+                .await
+                .unwrap();
+        tx.send(&[0xffu8][..]).await.unwrap();
+        let err = Error::from(sock.recv_string().await.unwrap_err());
+        assert!(err.zmq().is_none());
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    });
+}
+
+#[test]
+fn router_mandatory() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sock = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::ROUTER,
+            "inproc://router-mandatory",
+        )
+        .await
+        .unwrap();
+
+        // Dropped silently by default.
+        sock.send_multipart(&["bogus", "hello"]).await.unwrap();
+
+        // A ROUTER with ROUTER_MANDATORY is writable only once it has a peer.
+        sock.set_router_mandatory(true).unwrap();
+        let _peer = zmq_async::Socket::connect(
+            &ctx,
+            zmq_async::zmq::SocketType::DEALER,
+            "inproc://router-mandatory",
+        )
+        .await
+        .unwrap();
+        let err = sock.send_multipart(&["bogus", "hello"]).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::HostUnreachable, err.kind());
+        assert_eq!(Some(zmq::Error::EHOSTUNREACH), Error::from(err).zmq());
+    });
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+#[test]
+fn recv_after_term() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let pull = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.set_linger(0).unwrap();
+            sock.bind("inproc://eterm").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        // Terminating blocks until the socket is closed, so do it in another thread.
+        let mut term = ctx.clone();
+        let th = std::thread::spawn(move || term.destroy());
+
+        let res = tokio::time::timeout(Duration::from_secs(5), pull.recv_multipart())
+            .await
+            .unwrap();
+        let err = res.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+        assert_eq!(Some(zmq::Error::ETERM), zmq_async::Error::from(err).zmq());
+
+        // The setters and topology changes fail the same way once it's been seen.
+        let err = pull.set_linger(0).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+        let err = pull.connect_to("inproc://eterm-other").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+
+        drop(pull);
+        th.join().unwrap().unwrap();
+    });
+}
+
+#[test]
+fn try_recv_after_term() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let pull = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.set_linger(0).unwrap();
+            sock.bind("inproc://eterm-try").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let mut term = ctx.clone();
+        let th = std::thread::spawn(move || term.destroy());
+
+        let (waiting, polled) = futures::join!(pull.recv_multipart(), async {
+            loop {
+                match pull.try_recv_multipart() {
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        tokio::time::sleep(Duration::from_millis(1)).await
+                    }
+                    res => return res,
+                }
+            }
+        });
+        let err = polled.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+        assert_eq!(Some(zmq::Error::ETERM), zmq_async::Error::from(err).zmq());
+        assert_eq!(waiting.unwrap_err().kind(), std::io::ErrorKind::BrokenPipe);
+
+        drop(pull);
+        th.join().unwrap().unwrap();
+    });
+}
+
+#[test]
+fn try_send_after_term() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let push = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.set_linger(0).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let mut term = ctx.clone();
+        let th = std::thread::spawn(move || term.destroy());
+
+        let err = loop {
+            match push.try_send_multipart(&["x"]) {
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    tokio::time::sleep(Duration::from_millis(1)).await
+                }
+                res => break res.unwrap_err(),
+            }
+        };
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+        assert_eq!(
+            push.is_writable_now().unwrap_err().kind(),
+            std::io::ErrorKind::BrokenPipe
+        );
+
+        drop(push);
+        th.join().unwrap().unwrap();
+    });
+}
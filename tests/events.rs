@@ -0,0 +1,73 @@
+#[test]
+fn events() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://events")
+            .await
+            .unwrap();
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://events")
+                .await
+                .unwrap();
+
+        assert!(tx.events().unwrap().contains(zmq::POLLOUT));
+        assert!(!rx.events().unwrap().contains(zmq::POLLIN));
+
+        // A waiting receive isn't missed by reading the events in the meantime.
+        let (msgs, ()) = futures::join!(rx.recv_multipart(), async {
+            tx.send("a").await.unwrap();
+            assert!(rx.events().unwrap().contains(zmq::POLLIN));
+        });
+        assert_eq!(vec![b"a".to_vec()], msgs.unwrap());
+        assert!(!rx.events().unwrap().contains(zmq::POLLIN));
+    });
+}
+
+#[test]
+fn has_message() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::PULL,
+            "inproc://has_message",
+        )
+        .await
+        .unwrap();
+        let tx = zmq_async::Socket::connect(
+            &ctx,
+            zmq_async::zmq::SocketType::PUSH,
+            "inproc://has_message",
+        )
+        .await
+        .unwrap();
+
+        assert!(!rx.has_message().unwrap());
+        tx.send_multipart(&["a", "b"]).await.unwrap();
+        assert!(rx.has_message().unwrap());
+        assert!(rx.has_message().unwrap());
+
+        rx.recv_multipart().await.unwrap();
+        assert!(!rx.has_message().unwrap());
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn as_raw_fd() {
+    use std::os::unix::io::AsRawFd;
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+        assert_eq!(a.socket().get_fd().unwrap(), a.as_raw_fd());
+        assert_ne!(a.as_raw_fd(), b.as_raw_fd());
+    });
+}
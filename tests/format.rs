@@ -0,0 +1,42 @@
+#![cfg(all(feature = "serde_json", feature = "rmp-serde"))]
+
+use serde::{Deserialize, Serialize};
+use zmq_async::WireFormat;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn format() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://format").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://format").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        for &format in &[WireFormat::Json, WireFormat::MsgPack] {
+            tx.send_as(&Point { x: 1, y: 2 }, format).await.unwrap();
+            assert_eq!(Point { x: 1, y: 2 }, rx.recv_as(format).await.unwrap());
+        }
+
+        // The formats are not interchangeable.
+        tx.send_as(&Point { x: 1, y: 2 }, WireFormat::MsgPack)
+            .await
+            .unwrap();
+        let err = rx.recv_as::<Point>(WireFormat::Json).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    });
+}
@@ -0,0 +1,55 @@
+#[test]
+fn from_socket() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    // Set the sockets up before running any async code.
+    let (rx, tx) = {
+        let _guard = rt.enter();
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://from_socket").unwrap();
+            zmq_async::Socket::from_socket(sock).unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://from_socket").unwrap();
+            zmq_async::Socket::from_socket(sock).unwrap()
+        };
+        (rx, tx)
+    };
+
+    rt.block_on(async {
+        tx.send("a").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], rx.recv_multipart().await.unwrap());
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn from_fd_and_socket() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://from_fd").unwrap();
+            let fd = sock.get_fd().unwrap();
+            zmq_async::Socket::from_fd_and_socket(fd, sock).unwrap()
+        };
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://from_fd")
+                .await
+                .unwrap();
+
+        let (msgs, sent) = futures::join!(rx.recv_multipart(), async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            tx.send("a").await
+        });
+        sent.unwrap();
+        assert_eq!(vec![b"a".to_vec()], msgs.unwrap());
+    });
+}
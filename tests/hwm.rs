@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+#[test]
+fn backpressure() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .rcvhwm(1)
+            .bind("inproc://hwm")
+            .await
+            .unwrap();
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .sndhwm(1)
+            .connect("inproc://hwm")
+            .await
+            .unwrap();
+        assert!(tx.is_writable_now().unwrap());
+
+        // Fill the queues up to the high water marks.
+        let mut sent = 0;
+        while tx.try_send_multipart(&["x"]).is_ok() {
+            sent += 1;
+            assert!(sent < 100);
+        }
+        assert!(sent > 0);
+        assert!(!tx.is_writable_now().unwrap());
+
+        // A send stays pending under backpressure.
+        assert_eq!(
+            None,
+            tx.send_timeout(&["y"], Duration::from_millis(50))
+                .await
+                .unwrap()
+        );
+
+        for _ in 0..sent {
+            rx.recv_multipart().await.unwrap();
+        }
+        tokio::time::timeout(Duration::from_secs(5), tx.writable())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(tx.is_writable_now().unwrap());
+    });
+}
+
+#[test]
+fn send_multipart_backpressure() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .rcvhwm(1)
+            .bind("inproc://backpressure")
+            .await
+            .unwrap();
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .sndhwm(1)
+            .connect("inproc://backpressure")
+            .await
+            .unwrap();
+
+        let mut sent = 0;
+        while tx.try_send_multipart(&["x"]).is_ok() {
+            sent += 1;
+            assert!(sent < 100);
+        }
+
+        // The unsent message is handed back.
+        let unsent = tx
+            .send_multipart_backpressure(vec!["y", "z"], Some(Duration::from_millis(50)))
+            .await
+            .unwrap();
+        assert_eq!(Some(vec!["y", "z"]), unsent);
+
+        let (res, ()) = futures::join!(
+            tx.send_multipart_backpressure(unsent.unwrap(), None),
+            async {
+                for _ in 0..sent {
+                    rx.recv_multipart().await.unwrap();
+                }
+            }
+        );
+        assert_eq!(None, res.unwrap());
+        assert_eq!(
+            vec![b"y".to_vec(), b"z".to_vec()],
+            rx.recv_multipart().await.unwrap()
+        );
+    });
+}
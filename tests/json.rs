@@ -0,0 +1,36 @@
+#![cfg(feature = "serde_json")]
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn json() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://json").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://json").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_json(&Point { x: 1, y: 2 }).await.unwrap();
+        assert_eq!(Point { x: 1, y: 2 }, rx.recv_json().await.unwrap());
+
+        tx.send_str("not json").await.unwrap();
+        let err = rx.recv_json::<Point>().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    });
+}
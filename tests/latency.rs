@@ -0,0 +1,54 @@
+#![cfg(feature = "metrics")]
+
+use std::time::Duration;
+
+#[test]
+fn latency() {
+    let mut latency = zmq_async::Latency::new();
+    assert_eq!(None, latency.report());
+
+    for ms in (1..=100).rev() {
+        latency.record(Duration::from_millis(ms));
+    }
+    assert_eq!(
+        Some(zmq_async::LatencyReport {
+            count: 100,
+            min: Duration::from_millis(1),
+            max: Duration::from_millis(100),
+            p50: Duration::from_millis(50),
+            p99: Duration::from_millis(99),
+        }),
+        latency.report()
+    );
+
+    latency.reset();
+    latency.record(Duration::from_millis(7));
+    let report = latency.report().unwrap();
+    assert_eq!(1, report.count);
+    assert_eq!(Duration::from_millis(7), report.p50);
+    assert_eq!(Duration::from_millis(7), report.p99);
+}
+
+#[test]
+fn measure() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+        let mut latency = zmq_async::Latency::new();
+
+        for _ in 0..10 {
+            let msgs = latency
+                .measure(async {
+                    a.send("ping").await.unwrap();
+                    b.recv_multipart().await.unwrap()
+                })
+                .await;
+            assert_eq!(vec![b"ping".to_vec()], msgs);
+        }
+
+        let report = latency.report().unwrap();
+        assert_eq!(10, report.count);
+        assert!(report.min <= report.p50 && report.p50 <= report.p99 && report.p99 <= report.max);
+    });
+}
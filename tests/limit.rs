@@ -0,0 +1,33 @@
+#[test]
+fn recv_multipart_limited() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://limit").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://limit").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_multipart(&["a", "b", "c", "d", "e"]).await.unwrap();
+        tx.send_multipart(&["f", "g", "h"]).await.unwrap();
+
+        // Nothing is received with no room for a part.
+        let err = rx.recv_multipart_limited(0).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, err.kind());
+
+        let err = rx.recv_multipart_limited(3).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+
+        // The rest of the long message has been drained.
+        let msgs = rx.recv_multipart_limited(3).await.unwrap();
+        assert_eq!(vec![b"f".to_vec(), b"g".to_vec(), b"h".to_vec()], msgs);
+    });
+}
@@ -0,0 +1,55 @@
+#[test]
+fn send_built() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let router = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+            sock.bind("inproc://send_built").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let dealer = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            sock.connect("inproc://send_built").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        dealer.send("hello").await.unwrap();
+        let env = zmq_async::Envelope::recv(&router).await.unwrap();
+
+        let payload = b"payload".to_vec();
+        let msg = zmq_async::MessageBuilder::new()
+            .routing(&env)
+            .owned(b"owned".to_vec())
+            .empty()
+            .frame(&payload)
+            .constant(b"const");
+        assert_eq!(5, msg.len());
+
+        // The builder is only borrowed, so it can be sent again.
+        for _ in 0..2 {
+            router.send_built(&msg).await.unwrap();
+            assert_eq!(
+                vec![
+                    b"owned".to_vec(),
+                    vec![],
+                    b"payload".to_vec(),
+                    b"const".to_vec()
+                ],
+                dealer.recv_multipart().await.unwrap()
+            );
+        }
+
+        let mut msg = msg;
+        msg.clear();
+        assert!(msg.is_empty());
+        let msg = msg.routing(&env).frame(b"single");
+        router.send_built(&msg).await.unwrap();
+        assert_eq!(
+            vec![b"single".to_vec()],
+            dealer.recv_multipart().await.unwrap()
+        );
+    });
+}
@@ -0,0 +1,134 @@
+use futures::StreamExt;
+use std::time::Duration;
+
+#[test]
+fn accepted() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let srv = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::ROUTER,
+            "tcp://127.0.0.1:*",
+        )
+        .await
+        .unwrap();
+        let mut events = srv
+            .monitor(&ctx, zmq_async::zmq::SocketEvent::ACCEPTED)
+            .await
+            .unwrap();
+
+        let endpoint = srv.last_endpoint().unwrap();
+        let _cli = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::DEALER, &endpoint)
+            .await
+            .unwrap();
+
+        let (event, _) = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(zmq_async::zmq::SocketEvent::ACCEPTED, event);
+    });
+}
+
+#[test]
+fn wait_connected() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let publisher =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PUB, "tcp://127.0.0.1:*")
+                .await
+                .unwrap();
+
+        let err = publisher
+            .wait_connected(&ctx, Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+
+        let endpoint = publisher.last_endpoint().unwrap();
+        let subscriber = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let sub = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::SUB, &endpoint)
+                .await
+                .unwrap();
+            sub.subscribe("").unwrap();
+            sub
+        };
+
+        let (res, _sub) = futures::join!(
+            publisher.wait_connected(&ctx, Duration::from_secs(5)),
+            subscriber
+        );
+        res.unwrap();
+
+        // The monitor has been stopped, so a new one starts cleanly.
+        let mut events = publisher
+            .monitor(&ctx, zmq_async::zmq::SocketEvent::ACCEPTED)
+            .await
+            .unwrap();
+        let _sub = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::SUB, &endpoint)
+            .await
+            .unwrap();
+        let (event, _) = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(zmq_async::zmq::SocketEvent::ACCEPTED, event);
+    });
+}
+
+#[test]
+fn wait_for_peers() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let push =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PUSH, "tcp://127.0.0.1:*")
+                .await
+                .unwrap();
+        let endpoint = push.last_endpoint().unwrap();
+
+        let workers = async {
+            let mut workers = vec![];
+            for _ in 0..2 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                workers.push(
+                    zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PULL, &endpoint)
+                        .await
+                        .unwrap(),
+                );
+            }
+            workers
+        };
+        let (res, workers) = futures::join!(
+            push.wait_for_peers(&ctx, 2, Duration::from_secs(5)),
+            workers
+        );
+        res.unwrap();
+        assert_eq!(2, workers.len());
+
+        // One more worker connects, but not two.
+        let (res, _worker) = futures::join!(
+            push.wait_for_peers(&ctx, 2, Duration::from_millis(500)),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PULL, &endpoint)
+                    .await
+                    .unwrap()
+            }
+        );
+        let err = res.unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+        assert!(err.to_string().contains("only 1 of 2"), "{}", err);
+    });
+}
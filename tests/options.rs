@@ -0,0 +1,282 @@
+use futures::StreamExt;
+use std::time::Duration;
+
+#[test]
+fn subscribe() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sub = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::SUB).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        sub.subscribe("topic").unwrap();
+        sub.unsubscribe("topic").unwrap();
+
+        let push = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            push.subscribe("topic").unwrap_err().kind()
+        );
+    });
+}
+
+#[test]
+fn heartbeat() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sock = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        sock.set_heartbeat(
+            Duration::from_millis(100),
+            Duration::from_millis(300),
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(100, sock.socket().get_heartbeat_ivl().unwrap());
+        assert_eq!(300, sock.socket().get_heartbeat_timeout().unwrap());
+        assert_eq!(1000, sock.socket().get_heartbeat_ttl().unwrap());
+
+        let too_long = Duration::from_secs(u64::from(u32::MAX));
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            sock.set_heartbeat(too_long, too_long, too_long)
+                .unwrap_err()
+                .kind()
+        );
+    });
+}
+
+#[test]
+fn identity() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let router = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+            sock.bind("inproc://identity").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let dealer = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        dealer.set_identity(b"worker-1").unwrap();
+        assert_eq!(b"worker-1".to_vec(), dealer.identity().unwrap());
+        dealer.socket().connect("inproc://identity").unwrap();
+
+        dealer.send("hi").await.unwrap();
+        assert_eq!(
+            vec![b"worker-1".to_vec(), b"hi".to_vec()],
+            router.recv_multipart().await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn maxmsgsize() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        rx.set_maxmsgsize(16).unwrap();
+        assert_eq!(16, rx.get_maxmsgsize().unwrap());
+        rx.socket().bind("tcp://127.0.0.1:*").unwrap();
+
+        let mut events = rx
+            .monitor(&ctx, zmq_async::zmq::SocketEvent::DISCONNECTED)
+            .await
+            .unwrap();
+
+        // The oversized message may still be queued, so don't block the context on it.
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .linger(0)
+            .connect(&rx.last_endpoint().unwrap())
+            .await
+            .unwrap();
+        tx.send(&[0u8; 64][..]).await.unwrap();
+
+        let (event, _) = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(zmq_async::zmq::SocketEvent::DISCONNECTED, event);
+        assert!(rx
+            .recv_timeout(Duration::from_millis(100))
+            .await
+            .unwrap()
+            .is_none());
+    });
+}
+
+#[test]
+fn sockopt() {
+    const ZMQ_ROUTING_ID: i32 = 5;
+    const ZMQ_CONNECT_TIMEOUT: i32 = 79;
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sock = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        sock.set_sockopt_int(ZMQ_CONNECT_TIMEOUT, 1500).unwrap();
+        assert_eq!(1500, sock.get_sockopt_int(ZMQ_CONNECT_TIMEOUT).unwrap());
+
+        sock.set_sockopt_bytes(ZMQ_ROUTING_ID, b"peer").unwrap();
+        assert_eq!(b"peer".to_vec(), sock.identity().unwrap());
+        assert_eq!(
+            b"peer".to_vec(),
+            sock.get_sockopt_bytes(ZMQ_ROUTING_ID, 255).unwrap()
+        );
+
+        let err = sock.set_sockopt_int(-1, 0).unwrap_err();
+        assert_eq!(
+            Some(zmq_async::zmq::Error::EINVAL),
+            zmq_async::Error::from(err).zmq()
+        );
+    });
+}
+
+#[test]
+fn conflate() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let publisher = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUB)
+            .bind("inproc://conflate")
+            .await
+            .unwrap();
+
+        let sub = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::SUB).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        sub.set_conflate(true).unwrap();
+        sub.connect_to("inproc://conflate").unwrap();
+        sub.subscribe("").unwrap();
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            sub.set_conflate(false).unwrap_err().kind()
+        );
+
+        // Give the subscription time to reach the publisher.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        for i in 0..100 {
+            publisher.send(i.to_string()).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!("99", sub.recv_string().await.unwrap());
+        assert!(!sub.has_message().unwrap());
+
+        let conflated = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .conflate(true)
+            .bind("inproc://conflate-builder")
+            .await
+            .unwrap();
+        assert!(conflated.socket().is_conflate().unwrap());
+    });
+}
+
+#[test]
+fn immediate() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        // Find a port nothing listens on.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let endpoint = format!("tcp://127.0.0.1:{}", port);
+
+        let push = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        push.set_immediate(true).unwrap();
+        push.connect_to(&endpoint).unwrap();
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            push.set_immediate(false).unwrap_err().kind()
+        );
+
+        // Nothing is queued for the connection in progress.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), push.send("parked"))
+                .await
+                .is_err()
+        );
+
+        let pull = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, &endpoint)
+            .await
+            .unwrap();
+        push.send("sent").await.unwrap();
+        assert_eq!(b"sent", &pull.recv().await.unwrap()[..]);
+    });
+}
+
+#[test]
+fn tcp_options() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let router = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        router.set_backlog(16).unwrap();
+        assert_eq!(16, router.get_backlog().unwrap());
+        router.set_tcp_keepalive(1).unwrap();
+        assert_eq!(1, router.get_tcp_keepalive().unwrap());
+        router.set_tcp_keepalive_idle(60).unwrap();
+        assert_eq!(60, router.get_tcp_keepalive_idle().unwrap());
+        router.set_tcp_keepalive_cnt(3).unwrap();
+        assert_eq!(3, router.get_tcp_keepalive_cnt().unwrap());
+        router.set_tcp_keepalive_intvl(10).unwrap();
+        assert_eq!(10, router.get_tcp_keepalive_intvl().unwrap());
+        router.bind_to("tcp://127.0.0.1:*").unwrap();
+
+        let dealer = zmq_async::Socket::connect(
+            &ctx,
+            zmq_async::zmq::SocketType::DEALER,
+            &router.last_endpoint().unwrap(),
+        )
+        .await
+        .unwrap();
+        dealer.send("alive").await.unwrap();
+        assert_eq!(b"alive".to_vec(), router.recv_multipart().await.unwrap()[1]);
+    });
+}
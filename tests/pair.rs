@@ -0,0 +1,23 @@
+#[test]
+fn pair() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+        let (c, d) = zmq_async::pair().unwrap();
+
+        a.send("ping").await.unwrap();
+        assert_eq!(vec![b"ping".to_vec()], b.recv_multipart().await.unwrap());
+        b.send("pong").await.unwrap();
+        assert_eq!(vec![b"pong".to_vec()], a.recv_multipart().await.unwrap());
+
+        // The pairs don't share anything.
+        c.send("other").await.unwrap();
+        assert!(!a.has_message().unwrap());
+        assert!(!b.has_message().unwrap());
+        assert_eq!(vec![b"other".to_vec()], d.recv_multipart().await.unwrap());
+
+        // Nothing blocks on the undelivered message.
+        a.send("dropped").await.unwrap();
+    });
+}
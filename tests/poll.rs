@@ -0,0 +1,78 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Forwards a single message, written as a hand-rolled future.
+struct ForwardOne<'a> {
+    from: &'a zmq_async::Socket,
+    to: &'a zmq_async::Socket,
+    msgs: Option<Vec<Vec<u8>>>,
+}
+
+impl Future for ForwardOne<'_> {
+    type Output = std::io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            match &this.msgs {
+                None => match this.from.poll_recv_multipart(cx) {
+                    Poll::Ready(Ok(msgs)) => this.msgs = Some(msgs),
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                },
+                Some(msgs) => return this.to.poll_send_multipart(cx, msgs),
+            }
+        }
+    }
+}
+
+#[test]
+fn poll_api() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let mut socks = vec![];
+        for (kind, addr) in [
+            (zmq_async::zmq::SocketType::PULL, "inproc://poll-in"),
+            (zmq_async::zmq::SocketType::PUSH, "inproc://poll-out"),
+        ]
+        .iter()
+        {
+            let sock = ctx.socket(*kind).unwrap();
+            sock.bind(addr).unwrap();
+            socks.push(zmq_async::Socket::new(sock).await.unwrap());
+        }
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://poll-in").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.connect("inproc://poll-out").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let forward = ForwardOne {
+            from: &socks[0],
+            to: &socks[1],
+            msgs: None,
+        };
+        let send = async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            tx.send_multipart(&["a", "b"]).await.unwrap();
+        };
+        let (res, _) = futures::join!(forward, send);
+        res.unwrap();
+
+        assert_eq!(
+            vec![b"a".to_vec(), b"b".to_vec()],
+            rx.recv_multipart().await.unwrap()
+        );
+    });
+}
@@ -0,0 +1,109 @@
+use futures::FutureExt;
+
+#[test]
+fn proxy() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let frontend = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::ROUTER).unwrap();
+            sock.bind("inproc://proxy-front").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let backend = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::DEALER).unwrap();
+            sock.bind("inproc://proxy-back").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let client = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REQ).unwrap();
+            sock.connect("inproc://proxy-front").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let worker = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REP).unwrap();
+            sock.connect("inproc://proxy-back").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let proxy = zmq_async::proxy(&frontend, &backend);
+        let echo = async {
+            loop {
+                let msgs = worker.recv_multipart().await.unwrap();
+                worker.send_multipart(&msgs).await.unwrap();
+            }
+        };
+        let requests = async {
+            for i in 0..10u8 {
+                assert_eq!(vec![vec![i]], client.request(&[[i]]).await.unwrap());
+            }
+        };
+
+        futures::select! {
+            res = proxy.fuse() => panic!("proxy stopped: {:?}", res),
+            _ = echo.fuse() => unreachable!(),
+            _ = requests.fuse() => {},
+        }
+    });
+}
+
+#[test]
+fn proxy_steerable() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let frontend = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://steerable-front").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let backend = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.bind("inproc://steerable-back").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let control = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PAIR).unwrap();
+            sock.bind("inproc://steerable-control").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://steerable-front").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.connect("inproc://steerable-back").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let cmd = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PAIR).unwrap();
+            sock.connect("inproc://steerable-control").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let proxy = zmq_async::proxy_steerable(&frontend, &backend, &control);
+        let steps = async {
+            tx.send("a").await.unwrap();
+            assert_eq!(vec![b"a".to_vec()], rx.recv_multipart().await.unwrap());
+
+            cmd.send("PAUSE").await.unwrap();
+            tx.send("b").await.unwrap();
+            let timeout = std::time::Duration::from_millis(100);
+            assert_eq!(None, rx.recv_timeout(timeout).await.unwrap());
+
+            cmd.send("RESUME").await.unwrap();
+            assert_eq!(vec![b"b".to_vec()], rx.recv_multipart().await.unwrap());
+
+            cmd.send("TERMINATE").await.unwrap();
+        };
+
+        let (res, _) = futures::join!(proxy, steps);
+        res.unwrap();
+    });
+}
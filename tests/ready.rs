@@ -0,0 +1,38 @@
+use std::time::Duration;
+use zmq_async::Interest;
+
+#[test]
+fn ready() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://ready")
+            .await
+            .unwrap();
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://ready")
+                .await
+                .unwrap();
+
+        let ready = tx
+            .ready(Interest::READABLE | Interest::WRITABLE)
+            .await
+            .unwrap();
+        assert!(ready.is_writable());
+        assert!(!ready.is_readable());
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), rx.ready(Interest::READABLE))
+                .await
+                .is_err()
+        );
+
+        let (ready, ()) = futures::join!(rx.ready(Interest::READABLE), async {
+            tx.send("a").await.unwrap();
+        });
+        assert!(ready.unwrap().is_readable());
+        assert_eq!(vec![b"a".to_vec()], rx.try_recv_multipart().unwrap());
+    });
+}
@@ -0,0 +1,88 @@
+#[test]
+fn recv_multipart_into() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://recv_into").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://recv_into").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let mut buf = vec![];
+
+        tx.send_multipart(&["a", "bb", "ccc"]).await.unwrap();
+        rx.recv_multipart_into(&mut buf).await.unwrap();
+        assert_eq!(vec![b"a".to_vec(), b"bb".to_vec(), b"ccc".to_vec()], buf);
+
+        // No stale frames from the previous larger message.
+        tx.send_multipart(&["dddd"]).await.unwrap();
+        rx.recv_multipart_into(&mut buf).await.unwrap();
+        assert_eq!(vec![b"dddd".to_vec()], buf);
+    });
+}
+
+#[test]
+fn recv_multipart_msg_into() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (tx, rx) = zmq_async::pair().unwrap();
+
+        let mut buf = vec![];
+
+        tx.send_multipart(&["a", "bb", "ccc"]).await.unwrap();
+        rx.recv_multipart_msg_into(&mut buf).await.unwrap();
+        let frames: Vec<_> = buf.iter().map(|m| m.to_vec()).collect();
+        assert_eq!(vec![b"a".to_vec(), b"bb".to_vec(), b"ccc".to_vec()], frames);
+
+        // No stale frames from the previous larger message.
+        tx.send_multipart(&["dddd"]).await.unwrap();
+        rx.recv_multipart_msg_into(&mut buf).await.unwrap();
+        assert_eq!(1, buf.len());
+        assert_eq!(b"dddd", &buf[0][..]);
+        assert_eq!(10, rx.stats().recv_bytes);
+    });
+}
+
+#[test]
+fn recv_multipart_with() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+
+        a.send_multipart(&["a", "", "ccc"]).await.unwrap();
+        a.send("single").await.unwrap();
+
+        let mut frames = vec![];
+        b.recv_multipart_with(|frame, last| frames.push((frame.to_vec(), last)))
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![
+                (b"a".to_vec(), false),
+                (vec![], false),
+                (b"ccc".to_vec(), true)
+            ],
+            frames
+        );
+
+        let mut len = 0;
+        b.recv_multipart_with(|frame, last| {
+            assert!(last);
+            len += frame.len();
+        })
+        .await
+        .unwrap();
+        assert_eq!(6, len);
+        assert_eq!(10, b.stats().recv_bytes);
+    });
+}
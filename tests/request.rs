@@ -0,0 +1,33 @@
+#[test]
+fn request() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rep = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REP).unwrap();
+            sock.bind("inproc://request").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let req = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REQ).unwrap();
+            sock.connect("inproc://request").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let echo = async {
+            for _ in 0..3 {
+                let msgs = rep.recv_multipart().await.unwrap();
+                rep.send_multipart(&msgs).await.unwrap();
+            }
+        };
+        let client = async {
+            for i in 0..3u8 {
+                assert_eq!(vec![vec![i]], req.request(&[[i]]).await.unwrap());
+            }
+        };
+
+        futures::join!(echo, client);
+    });
+}
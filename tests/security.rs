@@ -0,0 +1,172 @@
+#[test]
+fn curve() {
+    if zmq::has("curve") != Some(true) {
+        return;
+    }
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let (server_public, server_secret) = zmq_async::curve_keypair().unwrap();
+        let (client_public, client_secret) = zmq_async::curve_keypair().unwrap();
+        assert_eq!(40, server_public.len());
+
+        let srv = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            let sock = zmq_async::Socket::new(sock).await.unwrap();
+            sock.set_curve_server(server_secret.as_bytes()).unwrap();
+            sock.socket().bind("tcp://127.0.0.1:*").unwrap();
+            sock
+        };
+        let cli = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            let sock = zmq_async::Socket::new(sock).await.unwrap();
+            sock.set_curve_client(
+                server_public.as_bytes(),
+                client_public.as_bytes(),
+                client_secret.as_bytes(),
+            )
+            .unwrap();
+            sock.socket()
+                .connect(&srv.last_endpoint().unwrap())
+                .unwrap();
+            sock
+        };
+
+        cli.send("secret").await.unwrap();
+        assert_eq!(
+            vec![b"secret".to_vec()],
+            srv.recv_multipart().await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn plain() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        // PLAIN servers need a ZAP handler to check the credentials.
+        let zap = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REP).unwrap();
+            sock.bind("inproc://zeromq.zap.01").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let srv = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            let sock = zmq_async::Socket::new(sock).await.unwrap();
+            sock.set_plain_server(true).unwrap();
+            sock.socket().bind("tcp://127.0.0.1:*").unwrap();
+            sock
+        };
+        let cli = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            let sock = zmq_async::Socket::new(sock).await.unwrap();
+            sock.set_plain_client("user", "password").unwrap();
+            sock.socket()
+                .connect(&srv.last_endpoint().unwrap())
+                .unwrap();
+            sock
+        };
+
+        assert_eq!(
+            Some("user".to_string()),
+            cli.socket().get_plain_username().unwrap().ok()
+        );
+
+        let auth = async {
+            let req = zap.recv_multipart().await.unwrap();
+            assert_eq!(b"PLAIN", &req[5][..]);
+            assert_eq!(b"user", &req[6][..]);
+            assert_eq!(b"password", &req[7][..]);
+            let rep: [&[u8]; 6] = [b"1.0", &req[1], b"200", b"OK", b"user", b""];
+            zap.send_multipart(&rep).await.unwrap();
+        };
+        let transfer = async {
+            cli.send("plain").await.unwrap();
+            assert_eq!(vec![b"plain".to_vec()], srv.recv_multipart().await.unwrap());
+        };
+
+        futures::join!(auth, transfer);
+    });
+}
+
+#[test]
+fn await_handshake() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let zap = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::REP).unwrap();
+            sock.bind("inproc://zeromq.zap.01").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let srv = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            let sock = zmq_async::Socket::new(sock).await.unwrap();
+            sock.set_plain_server(true).unwrap();
+            sock.socket().bind("tcp://127.0.0.1:*").unwrap();
+            sock
+        };
+        let endpoint = srv.last_endpoint().unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+
+        // The handshake of each client is checked by ZAP with the given status.
+        let client = |password: &'static str, status: &'static str| {
+            let (ctx, endpoint, zap) = (&ctx, &endpoint, &zap);
+            async move {
+                let cli = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+                cli.set_plain_username(Some("user")).unwrap();
+                cli.set_plain_password(Some(password)).unwrap();
+                cli.set_linger(0).unwrap();
+                cli.connect(endpoint).unwrap();
+
+                let req = zap.recv_multipart().await.unwrap();
+                assert_eq!(password.as_bytes(), &req[7][..]);
+                let rep: [&[u8]; 6] = [b"1.0", &req[1], status.as_bytes(), b"", b"user", b""];
+                zap.send_multipart(&rep).await.unwrap();
+                cli
+            }
+        };
+
+        let (res, _cli) = futures::join!(
+            srv.await_handshake(&ctx, timeout),
+            client("password", "200")
+        );
+        res.unwrap();
+
+        let (res, _cli) =
+            futures::join!(srv.await_handshake(&ctx, timeout), client("wrong", "400"));
+        let err = res.unwrap_err();
+        assert_eq!(std::io::ErrorKind::PermissionDenied, err.kind());
+        assert!(err.to_string().contains("ZAP status 400"));
+
+        // A client without PLAIN doesn't speak the mechanism of the server.
+        let cli = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, &endpoint);
+        let (res, _cli) = futures::join!(srv.await_handshake(&ctx, timeout), cli);
+        let err = res.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+        assert!(err.to_string().contains("mechanism mismatch"));
+
+        // The monitor has been stopped, so a new one starts cleanly.
+        let mut events = srv
+            .monitor(&ctx, zmq_async::zmq::SocketEvent::ACCEPTED)
+            .await
+            .unwrap();
+        let _cli = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, &endpoint)
+            .await
+            .unwrap();
+        let (event, _) = tokio::time::timeout(timeout, futures::StreamExt::next(&mut events))
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(zmq_async::zmq::SocketEvent::ACCEPTED, event);
+    });
+}
@@ -0,0 +1,154 @@
+#[test]
+fn recv_any() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let mut rxs = vec![];
+        let mut txs = vec![];
+        for i in 0..3 {
+            let addr = format!("inproc://select-{}", i);
+            let rx = {
+                let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+                sock.bind(&addr).unwrap();
+                zmq_async::Socket::new(sock).await.unwrap()
+            };
+            let tx = {
+                let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+                sock.connect(&addr).unwrap();
+                zmq_async::Socket::new(sock).await.unwrap()
+            };
+            rxs.push(rx);
+            txs.push(tx);
+        }
+        let rxs: Vec<_> = rxs.iter().collect();
+
+        let recv = zmq_async::recv_any(&rxs);
+        let send = async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            txs[2].send("two").await.unwrap();
+        };
+        let (res, _) = futures::join!(recv, send);
+        assert_eq!((2, vec![b"two".to_vec()]), res.unwrap());
+
+        txs[1].send("one").await.unwrap();
+        assert_eq!(
+            (1, vec![b"one".to_vec()]),
+            zmq_async::recv_any(&rxs).await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn socket_group() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let mut rxs = vec![];
+        let mut txs = vec![];
+        for i in 0..2 {
+            let addr = format!("inproc://group-{}", i);
+            let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, &addr)
+                .await
+                .unwrap();
+            let tx = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, &addr)
+                .await
+                .unwrap();
+            rxs.push(rx);
+            txs.push(tx);
+        }
+        let group = zmq_async::SocketGroup::new(rxs);
+
+        // The first socket is flooded while the second one trickles.
+        for _ in 0..100 {
+            txs[0].send("busy").await.unwrap();
+        }
+        for _ in 0..3 {
+            txs[1].send("quiet").await.unwrap();
+        }
+
+        let mut order = vec![];
+        for _ in 0..8 {
+            order.push(group.recv().await.unwrap().0);
+        }
+        assert_eq!(vec![0, 1, 0, 1, 0, 1, 0, 0], order);
+    });
+}
+
+#[test]
+fn socket_group_send_any() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx0 = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::PULL,
+            "inproc://send_any-0",
+        )
+        .await
+        .unwrap();
+        let rx1 = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::PULL,
+            "inproc://send_any-1",
+        )
+        .await
+        .unwrap();
+        let txs = vec![
+            zmq_async::Socket::connect(
+                &ctx,
+                zmq_async::zmq::SocketType::PUSH,
+                "inproc://send_any-0",
+            )
+            .await
+            .unwrap(),
+            zmq_async::Socket::connect(
+                &ctx,
+                zmq_async::zmq::SocketType::PUSH,
+                "inproc://send_any-1",
+            )
+            .await
+            .unwrap(),
+        ];
+        let group = zmq_async::SocketGroup::new(txs);
+
+        let mut order = vec![];
+        for _ in 0..4 {
+            order.push(group.send_any(&["spread"]).await.unwrap());
+        }
+        assert_eq!(vec![0, 1, 0, 1], order);
+        for rx in &[&rx0, &rx1] {
+            for _ in 0..2 {
+                assert_eq!(vec![b"spread".to_vec()], rx.recv_multipart().await.unwrap());
+            }
+        }
+
+        // A PUSH socket without a peer can't take a message, so the send waits for one.
+        let lone = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let group = zmq_async::SocketGroup::new(vec![lone.clone()]);
+        assert!(tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            group.send_any(&["parked"])
+        )
+        .await
+        .is_err());
+
+        let (sent, _) = futures::join!(group.send_any(&["parked"]), async {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            lone.connect_to("inproc://send_any-0").unwrap();
+        });
+        assert_eq!(0, sent.unwrap());
+        assert_eq!(
+            vec![b"parked".to_vec()],
+            rx0.recv_multipart().await.unwrap()
+        );
+    });
+}
@@ -0,0 +1,117 @@
+#[test]
+fn send_all() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://send_all").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://send_all").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send_all((0u8..3).map(|i| vec![i])).await.unwrap();
+        tx.send_all(std::iter::once("last")).await.unwrap();
+
+        assert_eq!(
+            vec![vec![0u8], vec![1u8], vec![2u8]],
+            rx.recv_multipart().await.unwrap()
+        );
+        assert_eq!(vec![b"last".to_vec()], rx.recv_multipart().await.unwrap());
+    });
+}
+
+#[test]
+fn send_vectored() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://send_vectored").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://send_vectored").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let frames = [std::io::IoSlice::new(b"a"), std::io::IoSlice::new(b"bc")];
+        tx.send_vectored(&frames).await.unwrap();
+        assert_eq!(
+            vec![b"a".to_vec(), b"bc".to_vec()],
+            rx.recv_multipart().await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn send_messages() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let pair = |endpoint: &'static str| {
+            let ctx = ctx.clone();
+            async move {
+                let a = ctx.socket(zmq_async::zmq::SocketType::PAIR).unwrap();
+                a.bind(endpoint).unwrap();
+                let b = ctx.socket(zmq_async::zmq::SocketType::PAIR).unwrap();
+                b.connect(endpoint).unwrap();
+                (
+                    zmq_async::Socket::new(a).await.unwrap(),
+                    zmq_async::Socket::new(b).await.unwrap(),
+                )
+            }
+        };
+        let (fwd_rx, tx) = pair("inproc://send_messages-in").await;
+        let (rx, fwd_tx) = pair("inproc://send_messages-out").await;
+
+        // Forward a message as it has been received.
+        tx.send_multipart(&["a", "", "ccc"]).await.unwrap();
+        let msgs = fwd_rx.recv_multipart_msg().await.unwrap();
+        fwd_tx.send_messages(msgs).await.unwrap();
+        assert_eq!(
+            vec![b"a".to_vec(), b"".to_vec(), b"ccc".to_vec()],
+            rx.recv_multipart().await.unwrap()
+        );
+
+        fwd_tx
+            .send_messages(vec![zmq::Message::from("single")])
+            .await
+            .unwrap();
+        assert_eq!(vec![b"single".to_vec()], rx.recv_multipart().await.unwrap());
+        assert_eq!(2, fwd_tx.stats().sent_messages);
+    });
+}
+
+#[test]
+fn send_const() {
+    static MARKER: &[u8] = b"a constant frame longer than the inline messages of libzmq";
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (tx, rx) = zmq_async::pair().unwrap();
+
+        tx.send_const(MARKER).await.unwrap();
+        tx.send_const(b"").await.unwrap();
+
+        // Inproc hands the message itself over, so it still references the static data.
+        let msg = rx.recv().await.unwrap();
+        assert_eq!(MARKER, &msg[..]);
+        assert_eq!(MARKER.as_ptr(), msg.as_ptr());
+        assert!(rx.recv().await.unwrap().is_empty());
+        assert_eq!(2, tx.stats().sent_messages);
+    });
+}
@@ -0,0 +1,36 @@
+use futures::StreamExt;
+
+#[test]
+fn forward() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://sink").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://sink").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        let msgs = vec![
+            vec![b"a".to_vec()],
+            vec![b"b".to_vec(), b"c".to_vec()],
+            vec![b"d".to_vec()],
+        ];
+
+        futures::stream::iter(msgs.clone().into_iter().map(Ok))
+            .forward(tx.outgoing())
+            .await
+            .unwrap();
+
+        for msg in msgs {
+            assert_eq!(msg, rx.recv_multipart().await.unwrap());
+        }
+    });
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+#[test]
+fn split() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    let local = tokio::task::LocalSet::new();
+    local.block_on(&rt, async {
+        let sock =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PAIR, "inproc://split")
+                .await
+                .unwrap();
+        let peer =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PAIR, "inproc://split")
+                .await
+                .unwrap();
+        let (tx, rx) = sock.split();
+
+        // The receiver waits from the start, before there is anything to receive.
+        let recv = tokio::task::spawn_local(async move {
+            let mut msgs = vec![];
+            for _ in 0..10 {
+                msgs.push(rx.recv_multipart().await.unwrap());
+            }
+            msgs
+        });
+        let echo = tokio::task::spawn_local(async move {
+            for _ in 0..10 {
+                let msg = peer.recv_multipart().await.unwrap();
+                peer.send_multipart(&msg).await.unwrap();
+            }
+        });
+        let send = tokio::task::spawn_local(async move {
+            for i in 0..10u8 {
+                tx.send_multipart(&[[i]]).await.unwrap();
+            }
+        });
+
+        let (msgs, echoed, sent) = tokio::time::timeout(Duration::from_secs(5), async {
+            futures::join!(recv, echo, send)
+        })
+        .await
+        .expect("a half was starved");
+        sent.unwrap();
+        echoed.unwrap();
+        let expected: Vec<_> = (0..10u8).map(|i| vec![vec![i]]).collect();
+        assert_eq!(expected, msgs.unwrap());
+    });
+}
@@ -0,0 +1,45 @@
+#[test]
+fn stats() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://stats").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://stats").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.send("abc").await.unwrap();
+        tx.send_multipart(&["de", "f"]).await.unwrap();
+
+        rx.readable().await.unwrap();
+        rx.recv_multipart().await.unwrap();
+        rx.recv_multipart_msg().await.unwrap();
+
+        assert_eq!(
+            zmq_async::SocketStats {
+                sent_messages: 2,
+                recv_messages: 0,
+                sent_bytes: 6,
+                recv_bytes: 0,
+            },
+            tx.stats()
+        );
+        assert_eq!(
+            zmq_async::SocketStats {
+                sent_messages: 0,
+                recv_messages: 2,
+                sent_bytes: 0,
+                recv_bytes: 6,
+            },
+            rx.stats()
+        );
+    });
+}
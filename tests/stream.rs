@@ -0,0 +1,128 @@
+use std::io::{Read, Write};
+use zmq_async::StreamEvent;
+
+#[test]
+fn stream() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let sock = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::STREAM,
+            "tcp://127.0.0.1:*",
+        )
+        .await
+        .unwrap();
+        let addr = sock.last_endpoint().unwrap().replace("tcp://", "");
+        let sock = zmq_async::StreamSocket::new(sock).unwrap();
+
+        // A plain TCP peer.
+        let peer = std::thread::spawn(move || {
+            let mut conn = std::net::TcpStream::connect(addr).unwrap();
+            conn.write_all(b"hello").unwrap();
+            let mut buf = [0; 5];
+            conn.read_exact(&mut buf).unwrap();
+            assert_eq!(b"world", &buf);
+        });
+
+        let id = match sock.recv_event().await.unwrap() {
+            StreamEvent::Connected(id) => id,
+            e => panic!("unexpected event {:?}", e),
+        };
+        assert_eq!(
+            StreamEvent::Data(id.clone(), b"hello".to_vec()),
+            sock.recv_event().await.unwrap()
+        );
+        sock.send_data(&id, b"world").await.unwrap();
+        assert_eq!(
+            StreamEvent::Disconnected(id),
+            sock.recv_event().await.unwrap()
+        );
+        peer.join().unwrap();
+
+        let pull = zmq_async::Socket::new(ctx.socket(zmq::SocketType::PULL).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            zmq_async::StreamSocket::new(pull).err().unwrap().kind()
+        );
+    });
+}
+
+#[test]
+fn stream_io() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("tcp://{}", listener.local_addr().unwrap());
+
+        let sock = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::STREAM, &endpoint)
+            .await
+            .unwrap();
+        let mut io = zmq_async::StreamIo::new(sock).unwrap();
+
+        // The first write waits for the connection.
+        let (written, accepted) = futures::join!(io.write_all(b"hello"), listener.accept());
+        written.unwrap();
+        let (mut conn, _) = accepted.unwrap();
+        assert!(io.peer().is_some());
+
+        let mut buf = [0; 5];
+        conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(b"hello", &buf);
+
+        // Reads are buffered across a frame larger than the read buffer.
+        conn.write_all(b"world!").await.unwrap();
+        let mut data = vec![];
+        let mut chunk = [0; 4];
+        while data.len() < 6 {
+            let n = io.read(&mut chunk).await.unwrap();
+            assert!(n > 0 && n <= 4);
+            data.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(b"world!".to_vec(), data);
+
+        drop(conn);
+        assert_eq!(0, io.read(&mut chunk).await.unwrap());
+        assert_eq!(
+            std::io::ErrorKind::BrokenPipe,
+            io.write_all(b"late").await.unwrap_err().kind()
+        );
+
+        // A bound socket talks to the first peer which connects.
+        let sock = zmq_async::Socket::bind(
+            &ctx,
+            zmq_async::zmq::SocketType::STREAM,
+            "tcp://127.0.0.1:*",
+        )
+        .await
+        .unwrap();
+        let addr = sock.last_endpoint().unwrap().replace("tcp://", "");
+        let mut io = zmq_async::StreamIo::new(sock).unwrap();
+        let mut conn = tokio::net::TcpStream::connect(addr).await.unwrap();
+        io.write_all(b"x").await.unwrap();
+
+        // Shutting down closes the connection.
+        io.shutdown().await.unwrap();
+        let mut rest = vec![];
+        tokio::select! {
+            read = conn.read_to_end(&mut rest) => read.unwrap(),
+            // libzmq closes the connection as the socket is used.
+            _ = async {
+                loop {
+                    io.get_ref().events().unwrap();
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+            } => unreachable!(),
+        };
+        assert_eq!(b"x".to_vec(), rest);
+    });
+}
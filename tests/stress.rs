@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+const MESSAGES: usize = 10000;
+
+async fn consume(sock: &zmq_async::Socket) -> usize {
+    let mut count = 0;
+    while sock
+        .recv_timeout(Duration::from_secs(1))
+        .await
+        .unwrap()
+        .is_some()
+    {
+        count += 1;
+    }
+    count
+}
+
+#[test]
+fn racing_consumers() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    let pull = |ctx: &zmq::Context| {
+        let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+        sock.connect("inproc://stress").unwrap();
+        sock
+    };
+
+    let sync_rx = pull(&ctx);
+    let sync_consumer = std::thread::spawn(move || {
+        sync_rx.set_rcvtimeo(1000).unwrap();
+        let mut count = 0;
+        while sync_rx.recv_multipart(0).is_ok() {
+            count += 1;
+        }
+        count
+    });
+
+    let async_count = rt.block_on(async {
+        let rx1 = zmq_async::Socket::new(pull(&ctx)).await.unwrap();
+        let rx2 = zmq_async::Socket::new(pull(&ctx)).await.unwrap();
+
+        let ctx = ctx.clone();
+        let producer = std::thread::spawn(move || {
+            let tx = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            tx.bind("inproc://stress").unwrap();
+            for i in 0..MESSAGES {
+                tx.send(&i.to_be_bytes()[..], 0).unwrap();
+            }
+        });
+
+        let (n1, n2) = futures::join!(consume(&rx1), consume(&rx2));
+        producer.join().unwrap();
+        n1 + n2
+    });
+
+    assert_eq!(MESSAGES, async_count + sync_consumer.join().unwrap());
+}
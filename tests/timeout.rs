@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+#[test]
+fn recv_timeout() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://timeout").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        assert_eq!(
+            None,
+            rx.recv_timeout(Duration::from_millis(100)).await.unwrap()
+        );
+
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://timeout").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        tx.send("hi").await.unwrap();
+
+        assert_eq!(
+            Some(vec![b"hi".to_vec()]),
+            rx.recv_timeout(Duration::from_secs(5)).await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn recv_deadline() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://deadline")
+                .await
+                .unwrap();
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://deadline")
+                .await
+                .unwrap();
+
+        // Both steps share the deadline, so the second one gets what the first one left.
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(100);
+        tx.send("hi").await.unwrap();
+        assert_eq!(
+            Some(vec![b"hi".to_vec()]),
+            rx.recv_deadline(deadline).await.unwrap()
+        );
+        assert_eq!(None, rx.recv_deadline(deadline).await.unwrap());
+        assert!(tokio::time::Instant::now() >= deadline);
+        // The task doesn't keep waiting once the deadline has passed.
+        assert!(format!("{:?}", rx).contains("read_waiting: false"));
+
+        // A deadline already passed still takes a message which is there.
+        tx.send("late").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(
+            Some(vec![b"late".to_vec()]),
+            rx.recv_deadline(deadline).await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn socket_timeouts() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://timeouts")
+                .await
+                .unwrap();
+        rx.set_rcvtimeo(Some(Duration::from_millis(50)));
+        assert_eq!(Some(Duration::from_millis(50)), rx.rcvtimeo());
+
+        let err = rx.recv_multipart().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+        let err = rx.recv().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+
+        // A PUSH socket without peers never accepts a message.
+        let tx = zmq_async::Socket::new(ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap())
+            .await
+            .unwrap();
+        tx.set_sndtimeo(Some(Duration::from_millis(50)));
+        let err = tx.send_multipart(&["a", "b"]).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+
+        tx.socket().connect("inproc://timeouts").unwrap();
+        tx.send("hi").await.unwrap();
+        assert_eq!(vec![b"hi".to_vec()], rx.recv_multipart().await.unwrap());
+
+        rx.set_rcvtimeo(None);
+        assert_eq!(None, rx.rcvtimeo());
+
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .recv_timeout(Some(Duration::from_millis(50)))
+            .bind("inproc://timeouts-builder")
+            .await
+            .unwrap();
+        assert_eq!(Some(Duration::from_millis(50)), rx.rcvtimeo());
+        let err = rx.recv_multipart().await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+
+        let tx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PUSH)
+            .send_timeout(Some(Duration::from_millis(50)))
+            .bind("inproc://timeouts-builder-push")
+            .await
+            .unwrap();
+        let err = tx.send("a").await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+    });
+}
+
+#[test]
+#[allow(deprecated)]
+fn builder_millis_timeouts() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        // The millisecond setters set the async timeouts too.
+        let rx = zmq_async::SocketBuilder::new(&ctx, zmq_async::zmq::SocketType::PULL)
+            .rcvtimeo(50)
+            .sndtimeo(-1)
+            .bind("inproc://timeouts-millis")
+            .await
+            .unwrap();
+        assert_eq!(Some(Duration::from_millis(50)), rx.rcvtimeo());
+        assert_eq!(None, rx.sndtimeo());
+        let err = tokio::time::timeout(Duration::from_secs(5), rx.recv_multipart())
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+    });
+}
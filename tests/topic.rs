@@ -0,0 +1,148 @@
+use futures::StreamExt;
+
+#[test]
+fn topic_stream() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let publisher = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUB).unwrap();
+            sock.bind("inproc://topic").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let sub = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::SUB).unwrap();
+            sock.connect("inproc://topic").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        sub.subscribe("news").unwrap();
+
+        // Give the subscription time to reach the publisher.
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        publisher
+            .send_multipart(&["weather", "rain"])
+            .await
+            .unwrap();
+        publisher.send_multipart(&["news", "a", "b"]).await.unwrap();
+        publisher.send_multipart(&["news"]).await.unwrap();
+
+        let mut topics = sub.topic_stream();
+        assert_eq!(
+            (b"news".to_vec(), vec![b"a".to_vec(), b"b".to_vec()]),
+            topics.next().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            (b"news".to_vec(), vec![]),
+            topics.next().await.unwrap().unwrap()
+        );
+    });
+}
+
+#[test]
+fn recv_subscription() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let xpub = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::XPUB).unwrap();
+            sock.bind("inproc://xpub").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let sub = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::SUB).unwrap();
+            sock.connect("inproc://xpub").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        sub.subscribe("news").unwrap();
+        assert_eq!(
+            zmq_async::Subscription {
+                subscribe: true,
+                topic: b"news".to_vec(),
+            },
+            xpub.recv_subscription().await.unwrap()
+        );
+
+        sub.unsubscribe("news").unwrap();
+        assert_eq!(
+            zmq_async::Subscription {
+                subscribe: false,
+                topic: b"news".to_vec(),
+            },
+            xpub.recv_subscription().await.unwrap()
+        );
+    });
+}
+
+#[test]
+fn last_value_cache() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let xpub = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::XPUB, "inproc://lvc")
+            .await
+            .unwrap();
+        let lvc = zmq_async::LastValueCache::new(xpub).unwrap();
+
+        // Nobody is subscribed yet, so these only go to the cache.
+        lvc.publish(b"news.a", &["old"]).await.unwrap();
+        lvc.publish(b"news.a", &["new"]).await.unwrap();
+        lvc.publish(b"news.b", &["b", "2"]).await.unwrap();
+        lvc.publish(b"sports", &["-"]).await.unwrap();
+        assert_eq!(Some(vec![b"new".to_vec()]), lvc.get(b"news.a"));
+
+        let sub = zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::SUB, "inproc://lvc")
+            .await
+            .unwrap();
+        sub.subscribe("news").unwrap();
+        assert_eq!(
+            zmq_async::Subscription {
+                subscribe: true,
+                topic: b"news".to_vec(),
+            },
+            lvc.recv_subscription().await.unwrap()
+        );
+
+        let mut msgs = vec![
+            sub.recv_multipart().await.unwrap(),
+            sub.recv_multipart().await.unwrap(),
+        ];
+        msgs.sort();
+        assert_eq!(
+            vec![
+                vec![b"news.a".to_vec(), b"new".to_vec()],
+                vec![b"news.b".to_vec(), b"b".to_vec(), b"2".to_vec()],
+            ],
+            msgs
+        );
+        assert!(!sub.has_message().unwrap());
+
+        // The same subscription from another subscriber arrives too, and the replay
+        // reaches both of them.
+        let late =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::SUB, "inproc://lvc")
+                .await
+                .unwrap();
+        late.subscribe("news").unwrap();
+        assert_eq!(
+            b"news".to_vec(),
+            lvc.recv_subscription().await.unwrap().topic
+        );
+        for sock in &[&late, &sub] {
+            let mut msgs = [
+                sock.recv_multipart().await.unwrap(),
+                sock.recv_multipart().await.unwrap(),
+            ];
+            msgs.sort();
+            assert_eq!(b"news.a".to_vec(), msgs[0][0]);
+            assert_eq!(b"news.b".to_vec(), msgs[1][0]);
+        }
+    });
+}
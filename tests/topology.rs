@@ -0,0 +1,140 @@
+#[test]
+fn connect_mid_flight() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let tx1 =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://topo-1")
+                .await
+                .unwrap();
+        let tx2 =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://topo-2")
+                .await
+                .unwrap();
+        let rx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://topo-1")
+                .await
+                .unwrap();
+
+        tx1.send("a").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], rx.recv_multipart().await.unwrap());
+
+        // Both the receive and the send to the new endpoint are waiting when it's connected.
+        let (msgs, sent, ()) = futures::join!(rx.recv_multipart(), tx2.send("b"), async {
+            rx.disconnect("inproc://topo-1").unwrap();
+            rx.connect_to("inproc://topo-2").unwrap();
+        });
+        assert_eq!(vec![b"b".to_vec()], msgs.unwrap());
+        sent.unwrap();
+
+        assert!(rx.disconnect("inproc://topo-1").is_err());
+        tx2.bind_to("inproc://topo-3").unwrap();
+        assert!(tx2.bind_to("inproc://topo-3").is_err());
+    });
+}
+
+#[test]
+fn unbind() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://topo-4")
+            .await
+            .unwrap();
+
+        rx.unbind("inproc://topo-4").unwrap();
+        assert!(rx.unbind("inproc://topo-4").is_err());
+        assert!(rx.unbind("inproc://topo\0").is_err());
+
+        // The endpoint is free again.
+        rx.bind_to("inproc://topo-4").unwrap();
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://topo-4")
+                .await
+                .unwrap();
+        tx.send("a").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], rx.recv_multipart().await.unwrap());
+    });
+}
+
+#[test]
+fn reconnect() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx =
+            zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "tcp://127.0.0.1:*")
+                .await
+                .unwrap();
+        let endpoint = rx.last_endpoint().unwrap();
+        let tx = zmq_async::Socket::new(ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap())
+            .await
+            .unwrap();
+
+        // Not connected yet.
+        tx.reconnect(&endpoint).unwrap();
+        tx.send("a").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], rx.recv_multipart().await.unwrap());
+
+        let (msgs, sent) = futures::join!(rx.recv_multipart(), async {
+            tx.reconnect(&endpoint).unwrap();
+            tx.send("b").await
+        });
+        sent.unwrap();
+        assert_eq!(vec![b"b".to_vec()], msgs.unwrap());
+
+        assert!(tx.reconnect("bogus://endpoint").is_err());
+    });
+}
+
+#[test]
+fn connect_all() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let mut rxs = vec![];
+        for i in 0..2 {
+            let addr = format!("inproc://connect_all-{}", i);
+            rxs.push(
+                zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, &addr)
+                    .await
+                    .unwrap(),
+            );
+        }
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        tx.connect_all(&["inproc://connect_all-0", "inproc://connect_all-1"])
+            .unwrap();
+        // PUSH deals the messages out to the peers in turn.
+        tx.send("a").await.unwrap();
+        tx.send("b").await.unwrap();
+        assert_eq!(vec![b"a".to_vec()], rxs[0].recv_multipart().await.unwrap());
+        assert_eq!(vec![b"b".to_vec()], rxs[1].recv_multipart().await.unwrap());
+
+        let err = tx
+            .connect_all(&[
+                "inproc://connect_all-0",
+                "bogus://endpoint",
+                "inproc://never",
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("bogus://endpoint"));
+        assert!(err.to_string().contains("1 of 3 endpoints connected"));
+
+        let res = tx.connect_each(&["bogus://endpoint", "inproc://connect_all-1"]);
+        assert_eq!(2, res.len());
+        assert!(res[0].is_err());
+        assert!(res[1].is_ok());
+    });
+}
@@ -0,0 +1,48 @@
+#[test]
+fn recv_multipart_verbose() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+
+        a.send_multipart(&["a", "", "ccc"]).await.unwrap();
+        a.send("single").await.unwrap();
+
+        assert_eq!(
+            (vec![b"a".to_vec(), vec![], b"ccc".to_vec()], true),
+            b.recv_multipart_verbose().await.unwrap()
+        );
+        assert_eq!(
+            (vec![b"single".to_vec()], true),
+            b.recv_multipart_verbose().await.unwrap()
+        );
+        assert_eq!(2, b.stats().recv_messages);
+        assert_eq!(10, b.stats().recv_bytes);
+    });
+}
+
+#[test]
+fn recv_frames() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let (a, b) = zmq_async::pair().unwrap();
+
+        a.send_multipart(&["a", ""]).await.unwrap();
+        a.send("single").await.unwrap();
+
+        let frame = |data: &[u8], more| zmq_async::Frame {
+            data: data.to_vec(),
+            more,
+        };
+        assert_eq!(
+            vec![frame(b"a", true), frame(b"", false)],
+            b.recv_frames().await.unwrap()
+        );
+        assert_eq!(
+            vec![frame(b"single", false)],
+            b.recv_frames().await.unwrap()
+        );
+        assert_eq!(7, b.stats().recv_bytes);
+    });
+}
@@ -0,0 +1,114 @@
+use futures::{
+    stream::FuturesUnordered,
+    task::{waker, ArcWake},
+    StreamExt,
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::Context,
+    time::Duration,
+};
+
+struct Count(AtomicUsize);
+
+impl ArcWake for Count {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn concurrent_recv() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PULL).unwrap();
+            sock.bind("inproc://waker").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+        let tx = {
+            let sock = ctx.socket(zmq_async::zmq::SocketType::PUSH).unwrap();
+            sock.connect("inproc://waker").unwrap();
+            zmq_async::Socket::new(sock).await.unwrap()
+        };
+
+        // Each future in `FuturesUnordered` is polled with its own waker.
+        let recvs: FuturesUnordered<_> = vec![rx.recv_multipart(), rx.recv_multipart()]
+            .into_iter()
+            .collect();
+        let send = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            tx.send("a").await.unwrap();
+            tx.send("b").await.unwrap();
+        };
+
+        let (msgs, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+            futures::join!(recvs.collect::<Vec<_>>(), send)
+        })
+        .await
+        .expect("a receiver was never woken up");
+
+        let mut msgs: Vec<_> = msgs.into_iter().map(|m| m.unwrap()).collect();
+        msgs.sort();
+        assert_eq!(vec![vec![b"a".to_vec()], vec![b"b".to_vec()]], msgs);
+    });
+}
+
+#[test]
+fn coalesced_cross_wake() {
+    // The reactor doesn't run until the test yields, so only the cross-wakes are seen.
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let ctx = zmq::Context::new();
+
+    rt.block_on(async {
+        let rx = zmq_async::Socket::bind(&ctx, zmq_async::zmq::SocketType::PULL, "inproc://cross")
+            .await
+            .unwrap();
+        let tx =
+            zmq_async::Socket::connect(&ctx, zmq_async::zmq::SocketType::PUSH, "inproc://cross")
+                .await
+                .unwrap();
+
+        let reads = Arc::new(Count(AtomicUsize::new(0)));
+        let read_waker = waker(reads.clone());
+        let write_waker = waker(Arc::new(Count(AtomicUsize::new(0))));
+
+        let recv = rx.recv_multipart();
+        futures::pin_mut!(recv);
+        assert!(recv
+            .as_mut()
+            .poll(&mut Context::from_waker(&read_waker))
+            .is_pending());
+
+        tx.send("a").await.unwrap();
+
+        // A PULL socket never becomes writable, and each poll sees the pending message.
+        let writable = rx.writable();
+        futures::pin_mut!(writable);
+        for _ in 0..3 {
+            assert!(writable
+                .as_mut()
+                .poll(&mut Context::from_waker(&write_waker))
+                .is_pending());
+        }
+        // The reader is woken up once, not on every poll of the writer. The wake list is
+        // drained on wake, so this doesn't tell whether the cross-wakes are coalesced.
+        assert_eq!(1, reads.0.load(Ordering::SeqCst));
+
+        assert!(recv
+            .as_mut()
+            .poll(&mut Context::from_waker(&read_waker))
+            .is_ready());
+    });
+}